@@ -0,0 +1,76 @@
+// Compares reading a large generated data file through the String-per-token
+// API (tokenize, then parse_all) with the lexer and parser that work on
+// slices of the source. Run with `cargo bench`.
+use lisp_interpreter_rs::*;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 5;
+
+// About 4 MB of records like a generated data file would hold
+fn generate() -> String {
+    let mut source = String::new();
+    let mut i = 0;
+    while source.len() < 4 << 20 {
+        source.push_str(&format!(
+            "(record :id {i} :name \"item {i}\" ; generated\n  :tags #(alpha beta gamma) :score {}.5\n  :parts ((part {} 3) (part #x{:x} 4)))\n",
+            i % 100,
+            i * 7,
+            i * 13
+        ));
+        i += 1;
+    }
+    source
+}
+
+// The fastest of several runs, in case the machine is busy
+fn time(mut run: impl FnMut()) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, bytes: usize, elapsed: Duration) {
+    let mb_per_second = bytes as f64 / (1 << 20) as f64 / elapsed.as_secs_f64();
+    println!("{:<32} {:>9.2?} {:>9.1} MB/s", name, elapsed, mb_per_second);
+}
+
+fn main() {
+    let source = generate();
+    let bytes = source.len();
+    println!("{} bytes, best of {} runs", bytes, RUNS);
+
+    let tokenize_strings = time(|| {
+        black_box(tokenize(black_box(&source)).len());
+    });
+    report("tokenize (String per token)", bytes, tokenize_strings);
+
+    let lex_slices = time(|| {
+        black_box(Lexer::new(black_box(&source)).count());
+    });
+    report("Lexer (slices)", bytes, lex_slices);
+
+    let parse_strings = time(|| {
+        let tokens = tokenize(black_box(&source));
+        let token_refs: Vec<&str> = tokens.iter().map(|s| s.as_str()).collect();
+        black_box(parse_all(&token_refs).unwrap().len());
+    });
+    report("tokenize + parse_all", bytes, parse_strings);
+
+    let parse_stream = time(|| {
+        let forms = Parser::new(Lexer::new(black_box(&source)));
+        black_box(forms.filter(|form| form.is_ok()).count());
+    });
+    report("Parser over Lexer", bytes, parse_stream);
+
+    println!(
+        "tokenizing {:.1}x faster, parsing {:.1}x faster",
+        tokenize_strings.as_secs_f64() / lex_slices.as_secs_f64(),
+        parse_strings.as_secs_f64() / parse_stream.as_secs_f64()
+    );
+}
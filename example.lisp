@@ -45,6 +45,15 @@
 (= 4 4)
 (= 3 7)
 
+; Chained comparisons
+(< 1 2 3)
+(<= 1 1 2)
+
+; Equality predicates
+(eq? (quote a) (quote a))
+(equal? (quote (1 (2 3))) (quote (1 (2 3))))
+(not (= 1 2))
+
 ; Comparisons with expressions
 (< (+ 1 2) 5)
 (> (* 2 3) 5)
@@ -109,3 +118,103 @@
 (define make-multiplier (lambda (factor) (lambda (x) (* x factor))))
 (define times3 (make-multiplier 3))
 (times3 10)
+
+; Strings
+(define greeting (string-append "hello" ", " "world"))
+(string-length greeting)
+(substring greeting 7 12)
+(string<? "apple" "banana")
+(number->string 255 16)
+
+; Characters
+(string-ref "lambda" 0)
+(char->integer #\A)
+(integer->char 955)
+(char-upcase #\x3bb)
+(char-alphabetic? #\space)
+(list->string (string->list "chars"))
+
+; Vectors
+(define v (vector 1 2 3))
+(vector-set! v 0 10)
+(vector-ref v 0)
+(vector-map (lambda (x) (* x x)) v)
+(vector->list #(a b c))
+
+; Hash tables
+(define stock (make-hash-table))
+(hash-table-set! stock "apples" 3)
+(hash-table-update! stock "apples" (lambda (n) (+ n 2)) 0)
+(hash-table-ref stock "pears" 0)
+(hash-table->alist stock)
+
+; Bytevectors
+(define frame (bytevector 1 2 3))
+(bytevector-u8-set! frame 0 255)
+(bytevector-append frame #u8(4 5))
+(string->utf8 "hi")
+
+; #t/#f literals (conditions follow the --dialect setting)
+(if #t 1 2)
+(not #f)
+
+; Type introspection
+(define scale (lambda (x k) (* x k)))
+(type-of scale)
+(procedure-arity scale)
+(procedure-name scale)
+(procedure-source scale)
+
+; Persistent collections
+(define defaults (hash-map (quote port) 80 (quote debug) #f))
+(define dev (assoc defaults (quote debug) #t))
+(get defaults (quote debug))
+(get dev (quote debug))
+(conj (pvector 1 2) 3)
+(contains? (hash-set "a" "b") "b")
+
+; Dates and time
+(define launch (string->date "2024-02-29T13:05:00+01:00"))
+(date->string (date-add launch 86400))
+(date-week-day launch)
+(define t0 (current-jiffy))
+(< (- (current-jiffy) t0) (jiffies-per-second))
+
+; Random numbers (pass --seed N for a different reproducible run)
+(random-seed! 2024)
+(random-integer 100)
+(shuffle (quote (a b c d)))
+(< (random-real) 1.0)
+
+; format
+(format "~a scored ~5,'0d points (~x in hex)" "Ada" 42 42)
+(format #t "written to stdout~%")
+
+; Keywords and keyword arguments
+(define make-server (lambda (name #:key (port 80) (tls #f)) (format "~a:~d tls=~a" name port tls)))
+(make-server "api" :port 8443 :tls #t)
+(make-server "web")
+
+; Quote shorthand: 'datum reads as (quote datum)
+'(1 2 3)
+'hello
+
+; Forms can span several lines
+(define sum-of-squares
+  (lambda (a b)
+    (+ (* a a) (* b b))))
+(sum-of-squares 3 4)
+
+; Comments can follow code, #| span several lines |#, or #; skip a datum
+(+ 1 #| one more |# 2) ; three
+(* 2 #;(this is ignored) 5)
+
+; Reader macros: #square n reads as n squared
+(set-reader-macro! "#square" (lambda (n) (* n n)))
+(+ #square 3 1)
+
+; Numeric literals: radix and exactness prefixes, exponents and ratios
+(+ #xFF #b1010 #o17)
+1e3
+#e2.5e1
+(* 3/4 2)
@@ -0,0 +1,148 @@
+use crate::strings::expect_args;
+use crate::types::Expr;
+use crate::vectors::{index_arg, length_arg, range_args};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub const BUILTINS: &[&str] = &[
+    "bytevector?",
+    "make-bytevector",
+    "bytevector",
+    "bytevector-length",
+    "bytevector-u8-ref",
+    "bytevector-u8-set!",
+    "bytevector-copy",
+    "bytevector-append",
+    "utf8->string",
+    "string->utf8",
+];
+
+// Built-in bytevector procedures. Returns None when `name` is not one of them.
+// Like vectors, bytevectors are shared and mutable.
+pub fn call(name: &str, args: &[Expr]) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "bytevector?" => {
+            expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::Bytevector(_))))
+        }
+        "make-bytevector" => make_bytevector(args),
+        "bytevector" => args
+            .iter()
+            .map(|e| byte_arg("bytevector", e))
+            .collect::<Result<Vec<u8>, String>>()
+            .map(new_bytevector),
+        "bytevector-length" => bytevector_length(args),
+        "bytevector-u8-ref" => bytevector_u8_ref(args),
+        "bytevector-u8-set!" => bytevector_u8_set(args),
+        "bytevector-copy" => bytevector_copy(args),
+        "bytevector-append" => bytevector_append(args),
+        "utf8->string" => utf8_to_string(args),
+        "string->utf8" => string_to_utf8(args),
+        _ => return None,
+    };
+    Some(result)
+}
+
+pub fn new_bytevector(bytes: Vec<u8>) -> Expr {
+    Expr::Bytevector(Rc::new(RefCell::new(bytes)))
+}
+
+pub fn byte_arg(name: &str, expr: &Expr) -> Result<u8, String> {
+    match expr {
+        Expr::Number(n) => {
+            u8::try_from(*n).map_err(|_| format!("{}: {} is not a byte (0-255)", name, n))
+        }
+        _ => Err(format!("{} requires a byte, got {}", name, expr.written())),
+    }
+}
+
+fn bytevector_arg<'a>(name: &str, expr: &'a Expr) -> Result<&'a Rc<RefCell<Vec<u8>>>, String> {
+    match expr {
+        Expr::Bytevector(bytes) => Ok(bytes),
+        _ => Err(format!(
+            "{} requires a bytevector, got {}",
+            name,
+            expr.written()
+        )),
+    }
+}
+
+fn make_bytevector(args: &[Expr]) -> Result<Expr, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err("make-bytevector requires 1 or 2 arguments".to_string());
+    }
+    let len = length_arg("make-bytevector", &args[0])?;
+    let fill = match args.get(1) {
+        Some(e) => byte_arg("make-bytevector", e)?,
+        None => 0,
+    };
+    Ok(new_bytevector(vec![fill; len]))
+}
+
+fn bytevector_length(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("bytevector-length", args, 1)?;
+    let bytes = bytevector_arg("bytevector-length", &args[0])?;
+    Ok(Expr::Number(bytes.borrow().len() as i64))
+}
+
+fn bytevector_u8_ref(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("bytevector-u8-ref", args, 2)?;
+    let bytes = bytevector_arg("bytevector-u8-ref", &args[0])?.borrow();
+    let k = index_arg("bytevector-u8-ref", &args[1], bytes.len(), false)?;
+    Ok(Expr::Number(bytes[k] as i64))
+}
+
+fn bytevector_u8_set(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("bytevector-u8-set!", args, 3)?;
+    let mut bytes = bytevector_arg("bytevector-u8-set!", &args[0])?.borrow_mut();
+    let k = index_arg("bytevector-u8-set!", &args[1], bytes.len(), false)?;
+    bytes[k] = byte_arg("bytevector-u8-set!", &args[2])?;
+    Ok(Expr::Unspecified)
+}
+
+fn bytevector_copy(args: &[Expr]) -> Result<Expr, String> {
+    if args.is_empty() {
+        return Err("bytevector-copy requires at least 1 argument".to_string());
+    }
+    let bytes = bytevector_arg("bytevector-copy", &args[0])?.borrow();
+    let (start, end) = range_args("bytevector-copy", args, 1, bytes.len())?;
+    Ok(new_bytevector(bytes[start..end].to_vec()))
+}
+
+fn bytevector_append(args: &[Expr]) -> Result<Expr, String> {
+    let mut result = Vec::new();
+    for arg in args {
+        result.extend_from_slice(&bytevector_arg("bytevector-append", arg)?.borrow());
+    }
+    Ok(new_bytevector(result))
+}
+
+fn utf8_to_string(args: &[Expr]) -> Result<Expr, String> {
+    if args.is_empty() {
+        return Err("utf8->string requires at least 1 argument".to_string());
+    }
+    let bytes = bytevector_arg("utf8->string", &args[0])?.borrow();
+    let (start, end) = range_args("utf8->string", args, 1, bytes.len())?;
+    match std::str::from_utf8(&bytes[start..end]) {
+        Ok(text) => Ok(Expr::Str(text.into())),
+        Err(err) => Err(format!("utf8->string: invalid UTF-8: {}", err)),
+    }
+}
+
+// Optional start/end are character indexes into the string
+fn string_to_utf8(args: &[Expr]) -> Result<Expr, String> {
+    if args.is_empty() {
+        return Err("string->utf8 requires at least 1 argument".to_string());
+    }
+    let text = match &args[0] {
+        Expr::Str(s) => s,
+        other => {
+            return Err(format!(
+                "string->utf8 requires a string, got {}",
+                other.written()
+            ));
+        }
+    };
+    let (start, end) = range_args("string->utf8", args, 1, text.chars().count())?;
+    let slice: String = text.chars().skip(start).take(end - start).collect();
+    Ok(new_bytevector(slice.into_bytes()))
+}
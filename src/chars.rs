@@ -0,0 +1,98 @@
+use crate::strings::expect_args;
+use crate::types::Expr;
+
+pub const BUILTINS: &[&str] = &[
+    "char?",
+    "char->integer",
+    "integer->char",
+    "char-alphabetic?",
+    "char-numeric?",
+    "char-whitespace?",
+    "char-upper-case?",
+    "char-lower-case?",
+    "char-upcase",
+    "char-downcase",
+    "char=?",
+    "char<?",
+    "char>?",
+    "char<=?",
+    "char>=?",
+];
+
+// Built-in character procedures. Returns None when `name` is not one of them.
+// Classification and case mapping follow Unicode, via Rust's `char`.
+pub fn call(name: &str, args: &[Expr]) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "char?" => expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::Char(_)))),
+        "char->integer" => char_arg(name, args).map(|c| Expr::Number(c as i64)),
+        "integer->char" => integer_to_char(args),
+        "char-alphabetic?" => char_arg(name, args).map(|c| Expr::Bool(c.is_alphabetic())),
+        "char-numeric?" => char_arg(name, args).map(|c| Expr::Bool(c.is_numeric())),
+        "char-whitespace?" => char_arg(name, args).map(|c| Expr::Bool(c.is_whitespace())),
+        "char-upper-case?" => char_arg(name, args).map(|c| Expr::Bool(c.is_uppercase())),
+        "char-lower-case?" => char_arg(name, args).map(|c| Expr::Bool(c.is_lowercase())),
+        "char-upcase" => char_arg(name, args).map(|c| Expr::Char(single(c, c.to_uppercase()))),
+        "char-downcase" => char_arg(name, args).map(|c| Expr::Char(single(c, c.to_lowercase()))),
+        "char=?" => compare_chars(name, args, |a, b| a == b),
+        "char<?" => compare_chars(name, args, |a, b| a < b),
+        "char>?" => compare_chars(name, args, |a, b| a > b),
+        "char<=?" => compare_chars(name, args, |a, b| a <= b),
+        "char>=?" => compare_chars(name, args, |a, b| a >= b),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn to_char(name: &str, expr: &Expr) -> Result<char, String> {
+    match expr {
+        Expr::Char(c) => Ok(*c),
+        _ => Err(format!(
+            "{} requires a character, got {}",
+            name,
+            expr.written()
+        )),
+    }
+}
+
+fn char_arg(name: &str, args: &[Expr]) -> Result<char, String> {
+    expect_args(name, args, 1)?;
+    to_char(name, &args[0])
+}
+
+// Case mappings that expand to several characters (e.g. 'ß' -> "SS") have no
+// single-character result, so the character is returned unchanged
+fn single(original: char, mut mapped: impl Iterator<Item = char>) -> char {
+    match (mapped.next(), mapped.next()) {
+        (Some(c), None) => c,
+        _ => original,
+    }
+}
+
+fn integer_to_char(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("integer->char", args, 1)?;
+    match &args[0] {
+        Expr::Number(n) => u32::try_from(*n)
+            .ok()
+            .and_then(char::from_u32)
+            .map(Expr::Char)
+            .ok_or(format!(
+                "integer->char: {} is not a Unicode scalar value",
+                n
+            )),
+        other => Err(format!(
+            "integer->char requires a number, got {}",
+            other.written()
+        )),
+    }
+}
+
+fn compare_chars(name: &str, args: &[Expr], cmp: fn(char, char) -> bool) -> Result<Expr, String> {
+    if args.len() < 2 {
+        return Err(format!("{} requires at least 2 arguments", name));
+    }
+    let chars = args
+        .iter()
+        .map(|e| to_char(name, e))
+        .collect::<Result<Vec<char>, String>>()?;
+    Ok(Expr::Bool(chars.windows(2).all(|w| cmp(w[0], w[1]))))
+}
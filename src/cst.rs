@@ -0,0 +1,289 @@
+use crate::parser::{DEFAULT_MAX_DEPTH, ParseError, Parser, Program, parse_tokens};
+use crate::readtable::Readtable;
+use crate::tokenizer::{Lexer, Span, Token, TokenKind, Trivia};
+use crate::types::Expr;
+use std::fmt;
+
+// A lossless concrete syntax tree. Every byte of the source belongs to exactly
+// one token or piece of trivia (whitespace and comments), so printing the tree
+// gives back the source unchanged, malformed input included. Trivia is kept
+// with the node that follows it; whatever comes after the last form is in
+// `trailing`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxTree<'a> {
+    pub forms: Vec<SyntaxNode<'a>>,
+    pub trailing: Vec<Trivia<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntaxNode<'a> {
+    // An atom, string or character, or a token that can't stand on its own
+    // (a ) with no list to close, an unterminated block comment)
+    Token {
+        leading: Vec<Trivia<'a>>,
+        token: Token<'a>,
+    },
+    // (...), #(...) or #u8(...), as told by the opening token. `trailing` is
+    // the trivia before the closing paren, which is None if the input ends
+    // first.
+    List {
+        leading: Vec<Trivia<'a>>,
+        open: Token<'a>,
+        items: Vec<SyntaxNode<'a>>,
+        trailing: Vec<Trivia<'a>>,
+        close: Option<Token<'a>>,
+    },
+    // 'datum, or another reader macro before its datum; the datum is None if
+    // the input ends first
+    Quote {
+        leading: Vec<Trivia<'a>>,
+        quote: Token<'a>,
+        datum: Option<Box<SyntaxNode<'a>>>,
+    },
+}
+
+// Builds the syntax tree of `source`. This never fails: problems are left in
+// the tree for `lower` to report.
+pub fn parse_cst(source: &str) -> SyntaxTree<'_> {
+    parse_cst_with(source, &Readtable::default())
+}
+
+// Builds the syntax tree of `source` as read with the reader macros in
+// `readtable`; lower it with the same table
+pub fn parse_cst_with<'a>(source: &'a str, readtable: &Readtable) -> SyntaxTree<'a> {
+    let mut builder = Builder {
+        source,
+        readtable,
+        tokens: Lexer::new(source).with_readtable(readtable).collect(),
+        cursor: 0,
+        depth: 0,
+        position: Span {
+            line: 1,
+            column: 1,
+            ..Span::default()
+        },
+    };
+    let mut forms = Vec::new();
+    while builder.cursor < builder.tokens.len() {
+        forms.push(builder.node());
+    }
+    let trailing = builder.trivia_up_to(source.len());
+    SyntaxTree { forms, trailing }
+}
+
+struct Builder<'a, 'r> {
+    source: &'a str,
+    readtable: &'r Readtable,
+    tokens: Vec<Token<'a>>,
+    cursor: usize,
+    // How many lists and quotes the node being built is inside
+    depth: usize,
+    // Just past the last token taken
+    position: Span,
+}
+
+impl<'a> Builder<'a, '_> {
+    // Takes the trivia from the current position up to the byte offset `end`
+    fn trivia_up_to(&mut self, end: usize) -> Vec<Trivia<'a>> {
+        let mut lexer =
+            Lexer::starting_at(self.source, self.position).with_readtable(self.readtable);
+        let mut trivia = Vec::new();
+        loop {
+            let before = lexer.position();
+            match lexer.next_trivia() {
+                Some(piece) if piece.span.start < end => trivia.push(piece),
+                _ => {
+                    self.position = before;
+                    return trivia;
+                }
+            }
+        }
+    }
+
+    // The trivia before the next token, and the token
+    fn take(&mut self) -> (Vec<Trivia<'a>>, Token<'a>) {
+        let token = self.tokens[self.cursor];
+        let leading = self.trivia_up_to(token.span.start);
+        self.cursor += 1;
+        self.position = token.end_position();
+        (leading, token)
+    }
+
+    fn node(&mut self) -> SyntaxNode<'a> {
+        let (leading, token) = self.take();
+        if self.depth >= DEFAULT_MAX_DEPTH {
+            // Too deep to build safely: the rest is kept as flat tokens, so
+            // the tree still prints the source, and `lower` reports it
+            return SyntaxNode::Token { leading, token };
+        }
+        self.depth += 1;
+        let node = self.nested(leading, token);
+        self.depth -= 1;
+        node
+    }
+
+    fn nested(&mut self, leading: Vec<Trivia<'a>>, token: Token<'a>) -> SyntaxNode<'a> {
+        match token.kind {
+            TokenKind::LParen | TokenKind::VectorOpen | TokenKind::BytevectorOpen => {
+                let mut items = Vec::new();
+                loop {
+                    match self.tokens.get(self.cursor) {
+                        Some(next) if next.kind == TokenKind::RParen => {
+                            let (trailing, close) = self.take();
+                            return SyntaxNode::List {
+                                leading,
+                                open: token,
+                                items,
+                                trailing,
+                                close: Some(close),
+                            };
+                        }
+                        Some(_) => items.push(self.node()),
+                        None => {
+                            let trailing = self.trivia_up_to(self.source.len());
+                            return SyntaxNode::List {
+                                leading,
+                                open: token,
+                                items,
+                                trailing,
+                                close: None,
+                            };
+                        }
+                    }
+                }
+            }
+            TokenKind::Quote => self.macro_datum(leading, token),
+            TokenKind::Atom if self.readtable.contains(token.text) => {
+                self.macro_datum(leading, token)
+            }
+            _ => SyntaxNode::Token { leading, token },
+        }
+    }
+
+    // The node of a reader macro and the datum after it
+    fn macro_datum(&mut self, leading: Vec<Trivia<'a>>, token: Token<'a>) -> SyntaxNode<'a> {
+        let datum = (self.cursor < self.tokens.len()).then(|| Box::new(self.node()));
+        SyntaxNode::Quote {
+            leading,
+            quote: token,
+            datum,
+        }
+    }
+}
+
+impl<'a> SyntaxTree<'a> {
+    // The forms as expressions, with the spans of their lists, reporting the
+    // first problem the way `parse_program` would
+    pub fn lower(&self) -> Result<Program, ParseError> {
+        self.lower_with(&Readtable::default())
+    }
+
+    // Lowers with the reader macros in `readtable`, which should be the table
+    // the tree was built with
+    pub fn lower_with(&self, readtable: &Readtable) -> Result<Program, ParseError> {
+        let mut tokens = Vec::new();
+        for form in &self.forms {
+            form.collect_tokens(&mut tokens);
+        }
+        parse_tokens(tokens.into_iter(), readtable)
+    }
+}
+
+impl<'a> SyntaxNode<'a> {
+    pub fn leading(&self) -> &[Trivia<'a>] {
+        match self {
+            SyntaxNode::Token { leading, .. }
+            | SyntaxNode::List { leading, .. }
+            | SyntaxNode::Quote { leading, .. } => leading,
+        }
+    }
+
+    // From the first token of the node to its last, leaving out the leading
+    // trivia
+    pub fn span(&self) -> Span {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+        let (first, last) = (tokens[0], tokens[tokens.len() - 1]);
+        Span {
+            end: last.span.end,
+            ..first.span
+        }
+    }
+
+    // The node as an expression
+    pub fn lower(&self) -> Result<Expr, ParseError> {
+        self.lower_with(&Readtable::default())
+    }
+
+    pub fn lower_with(&self, readtable: &Readtable) -> Result<Expr, ParseError> {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+        let mut parser = Parser::new(tokens.into_iter()).with_readtable(readtable);
+        parser.parse_value().map(|(expr, _)| expr)
+    }
+
+    fn collect_tokens(&self, tokens: &mut Vec<Token<'a>>) {
+        match self {
+            SyntaxNode::Token { token, .. } => tokens.push(*token),
+            SyntaxNode::List {
+                open, items, close, ..
+            } => {
+                tokens.push(*open);
+                for item in items {
+                    item.collect_tokens(tokens);
+                }
+                tokens.extend(close);
+            }
+            SyntaxNode::Quote { quote, datum, .. } => {
+                tokens.push(*quote);
+                if let Some(datum) = datum {
+                    datum.collect_tokens(tokens);
+                }
+            }
+        }
+    }
+}
+
+fn write_trivia(f: &mut fmt::Formatter<'_>, trivia: &[Trivia]) -> fmt::Result {
+    trivia.iter().try_for_each(|t| f.write_str(t.text))
+}
+
+// Writes the node back out exactly as it was read
+impl fmt::Display for SyntaxNode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_trivia(f, self.leading())?;
+        match self {
+            SyntaxNode::Token { token, .. } => f.write_str(token.text),
+            SyntaxNode::List {
+                open,
+                items,
+                trailing,
+                close,
+                ..
+            } => {
+                f.write_str(open.text)?;
+                for item in items {
+                    write!(f, "{}", item)?;
+                }
+                write_trivia(f, trailing)?;
+                close.map_or(Ok(()), |close| f.write_str(close.text))
+            }
+            SyntaxNode::Quote { quote, datum, .. } => {
+                f.write_str(quote.text)?;
+                datum
+                    .as_ref()
+                    .map_or(Ok(()), |datum| write!(f, "{}", datum))
+            }
+        }
+    }
+}
+
+// Writes the source back out byte for byte
+impl fmt::Display for SyntaxTree<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for form in &self.forms {
+            write!(f, "{}", form)?;
+        }
+        write_trivia(f, &self.trailing)
+    }
+}
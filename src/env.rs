@@ -1,9 +1,188 @@
+use crate::random::Rng;
+use crate::readtable::Readtable;
+use crate::source_map::SourceMap;
+use crate::symbol::Symbol;
+use crate::tokenizer::Span;
 use crate::types::Expr;
+use std::cell::{Cell, RefCell, RefMut};
 use std::collections::HashMap;
+use std::rc::{Rc, Weak};
 
-pub type Env = HashMap<String, Expr>;
+// Which values count as true in conditionals (if, not, ...)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Dialect {
+    // C-like: false and 0 are false, other booleans and numbers are true,
+    // and any other value is an error
+    #[default]
+    CLike,
+    // Scheme: only #f is false; every other value (0, '(), "") is true
+    Scheme,
+}
+
+impl Dialect {
+    pub fn is_truthy(self, value: &Expr) -> Result<bool, String> {
+        match (self, value) {
+            (_, Expr::Bool(b)) => Ok(*b),
+            (Dialect::Scheme, _) => Ok(true),
+            (Dialect::CLike, Expr::Number(n)) => Ok(*n != 0),
+            (Dialect::CLike, Expr::Real(x)) => Ok(*x != 0.0),
+            (Dialect::CLike, _) => Err("condition must be a boolean or a number".to_string()),
+        }
+    }
+}
+
+// Variable bindings plus the interpreter settings they are evaluated under.
+// Closures copy the environment, settings included. The random number
+// generator is shared between the copies instead, so procedures draw from the
+// same sequence as the code that created them. So are the span of the last
+// form that failed, so an error raised inside a procedure can be traced back
+// to the file, and the readtable that reader macros are registered in.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Env {
+    vars: HashMap<Symbol, Expr>,
+    dialect: Dialect,
+    rng: Rc<RefCell<Rng>>,
+    source_map: Rc<SourceMap>,
+    error_span: Rc<Cell<Option<Span>>>,
+    readtable: SharedReadtable,
+}
+
+// The copies closures keep only refer to the readtable weakly. A reader
+// macro defined in Lisp holds its procedure, whose copy would otherwise keep
+// the readtable, and so the macro itself, alive forever.
+#[derive(Debug, Clone)]
+enum SharedReadtable {
+    Strong(Rc<RefCell<Readtable>>),
+    Weak(Weak<RefCell<Readtable>>),
+}
+
+impl SharedReadtable {
+    // The readtable, or a fresh one if every environment that owned it is
+    // gone (changes to that one are lost)
+    fn get(&self) -> Rc<RefCell<Readtable>> {
+        match self {
+            SharedReadtable::Strong(table) => table.clone(),
+            SharedReadtable::Weak(table) => table.upgrade().unwrap_or_default(),
+        }
+    }
+}
+
+impl Default for SharedReadtable {
+    fn default() -> Self {
+        SharedReadtable::Strong(Rc::default())
+    }
+}
+
+impl PartialEq for SharedReadtable {
+    fn eq(&self, other: &Self) -> bool {
+        *self.get().borrow() == *other.get().borrow()
+    }
+}
+
+impl Env {
+    pub fn new() -> Env {
+        Env::default()
+    }
+
+    pub fn with_dialect(dialect: Dialect) -> Env {
+        Env {
+            dialect,
+            ..Env::default()
+        }
+    }
+
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    pub fn set_dialect(&mut self, dialect: Dialect) {
+        self.dialect = dialect;
+    }
+
+    // Restarts the random number generator from `seed`, for this environment
+    // and every closure created from it
+    pub fn set_random_seed(&self, seed: u64) {
+        *self.rng.borrow_mut() = Rng::new(seed);
+    }
+
+    pub(crate) fn rng(&self) -> RefMut<'_, Rng> {
+        self.rng.borrow_mut()
+    }
+
+    // Registers the spans of the code about to be evaluated. They replace
+    // the spans registered before, whose lists may have been freed since.
+    pub fn set_source_map(&mut self, source_map: SourceMap) {
+        self.source_map = Rc::new(source_map);
+    }
+
+    // The span of the innermost form whose evaluation failed, if it came from
+    // registered source. Taking it clears it.
+    pub fn take_error_span(&self) -> Option<Span> {
+        self.error_span.take()
+    }
+
+    // Called as each list form finishes: the first form to fail (the
+    // innermost) records its span, and any success clears a stale one
+    pub(crate) fn note_result<T, E>(&self, items: &[Expr], result: &Result<T, E>) {
+        if result.is_ok() {
+            self.error_span.set(None);
+        } else if self.error_span.get().is_none() {
+            self.error_span.set(self.source_map.span_of(items));
+        }
+    }
+
+    // Lambda bodies are copies of the parsed code, so a lambda keeps the
+    // spans of its copy for as long as it lives
+    pub(crate) fn copy_spans(&self, original: &Expr, copy: &Expr) -> Rc<SourceMap> {
+        let mut spans = SourceMap::new();
+        spans.copy_spans(&self.source_map, original, copy);
+        Rc::new(spans)
+    }
+
+    // Evaluating a lambda body looks its forms up in the lambda's spans
+    pub(crate) fn set_spans(&mut self, spans: Rc<SourceMap>) {
+        self.source_map = spans;
+    }
+
+    // The reader macros in effect for code read after this point
+    pub fn readtable(&self) -> Readtable {
+        self.readtable.get().borrow().clone()
+    }
+
+    pub fn update_readtable<T>(&self, update: impl FnOnce(&mut Readtable) -> T) -> T {
+        update(&mut self.readtable.get().borrow_mut())
+    }
+
+    // A copy of the environment for a closure to keep, which refers to the
+    // readtable weakly
+    pub(crate) fn closure_copy(&self) -> Env {
+        let readtable = match &self.readtable {
+            SharedReadtable::Strong(table) => SharedReadtable::Weak(Rc::downgrade(table)),
+            weak => weak.clone(),
+        };
+        Env {
+            readtable,
+            ..self.clone()
+        }
+    }
+
+    pub fn get(&self, name: &Symbol) -> Option<&Expr> {
+        self.vars.get(name)
+    }
+
+    pub fn contains_key(&self, name: &Symbol) -> bool {
+        self.vars.contains_key(name)
+    }
+
+    pub fn insert(&mut self, name: Symbol, value: Expr) -> Option<Expr> {
+        self.vars.insert(name, value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Symbol, &Expr)> {
+        self.vars.iter()
+    }
+}
 
 pub fn default_env() -> Env {
-    let env = HashMap::new();
-    env
+    Env::new()
 }
@@ -0,0 +1,187 @@
+use crate::types::Expr;
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+// eqv? (and eq?, which is the same procedure): identity comparison where
+// values have an identity.
+// - Numbers, booleans, characters, symbols, keywords and dates are immediate
+//   values, so they compare by value (with no boxed numbers, eq? and eqv?
+//   always agree)
+// - Strings are compared by identity: a literal or a variable holding a string
+//   is eqv? to itself, but two strings built separately are not
+// - Lambdas are compared by identity: every evaluation of a lambda form creates
+//   a new procedure, and copies of that value share the same body
+// - Vectors, bytevectors and hash tables are mutable, so they are compared by
+//   identity
+// - Persistent maps, sets and vectors are immutable but may be large, so
+//   eqv? only checks whether both are the same version
+// - Lists have no identity to compare. A list is a plain Vec that is copied
+//   whenever it is passed or stored, so "the same list" can't be told apart
+//   from an equal one. Lists are eqv? when their elements are pairwise eqv?,
+//   which keeps (eqv? x x) true and makes '() eqv? to '(); unlike equal?, it
+//   does not look inside strings, vectors or other containers in them.
+pub fn is_eqv(a: &Expr, b: &Expr) -> bool {
+    match (a, b) {
+        (Expr::Number(x), Expr::Number(y)) => x == y,
+        // Reals are the same when their bits are, so +nan.0 is eqv? to
+        // itself while 0.0 and -0.0 differ; 2 and 2.0 are not eqv?
+        (Expr::Real(x), Expr::Real(y)) => x.to_bits() == y.to_bits(),
+        (Expr::Bool(x), Expr::Bool(y)) => x == y,
+        (Expr::Symbol(x), Expr::Symbol(y)) => x == y,
+        (Expr::Keyword(x), Expr::Keyword(y)) => x == y,
+        (Expr::Char(x), Expr::Char(y)) => x == y,
+        (Expr::Date(x), Expr::Date(y)) => x == y,
+        (Expr::Str(x), Expr::Str(y)) => Rc::ptr_eq(x, y),
+        (Expr::List(xs), Expr::List(ys)) => {
+            xs.len() == ys.len() && xs.iter().zip(ys).all(|(x, y)| is_eqv(x, y))
+        }
+        (Expr::Vector(x), Expr::Vector(y)) => Rc::ptr_eq(x, y),
+        (Expr::Bytevector(x), Expr::Bytevector(y)) => Rc::ptr_eq(x, y),
+        (Expr::HashTable(x), Expr::HashTable(y)) => Rc::ptr_eq(x, y),
+        (Expr::Map(x), Expr::Map(y)) => x.ptr_eq(y),
+        (Expr::Set(x), Expr::Set(y)) => x.ptr_eq(y),
+        (Expr::PVector(x), Expr::PVector(y)) => x.ptr_eq(y),
+        (Expr::Builtin(x), Expr::Builtin(y)) => x == y,
+        (Expr::Unspecified, Expr::Unspecified) | (Expr::Eof, Expr::Eof) => true,
+        (Expr::Error(x), Expr::Error(y)) => x == y,
+        (Expr::Lambda { body: x, .. }, Expr::Lambda { body: y, .. }) => Rc::ptr_eq(x, y),
+        _ => false,
+    }
+}
+
+// equal?: structural comparison.
+// Recurses into lists, vectors and persistent collections and compares strings
+// and bytevectors by content; everything else (including procedures and hash
+// tables) falls back to eqv?. Maps and sets are equal when they have equal?
+// keys (and values), whatever order they were built in.
+pub fn is_equal(a: &Expr, b: &Expr) -> bool {
+    equal_with(a, b, &mut HashSet::new())
+}
+
+// The pairs of vectors met so far, by address. vector-set! can put a vector
+// inside itself, so each pair is only compared the first time it is met;
+// meeting it again (around a cycle) counts as equal, and the first
+// comparison decides.
+type Compared = HashSet<(*const (), *const ())>;
+
+fn equal_with(a: &Expr, b: &Expr, compared: &mut Compared) -> bool {
+    match (a, b) {
+        (Expr::Str(x), Expr::Str(y)) => x == y,
+        (Expr::Bytevector(x), Expr::Bytevector(y)) => x == y,
+        (Expr::Vector(x), Expr::Vector(y)) => {
+            let pair = (Rc::as_ptr(x).cast(), Rc::as_ptr(y).cast());
+            if Rc::ptr_eq(x, y) || !compared.insert(pair) {
+                return true;
+            }
+            let (xs, ys) = (x.borrow(), y.borrow());
+            xs.len() == ys.len()
+                && xs
+                    .iter()
+                    .zip(ys.iter())
+                    .all(|(x, y)| equal_with(x, y, compared))
+        }
+        (Expr::List(xs), Expr::List(ys)) => {
+            xs.len() == ys.len() && xs.iter().zip(ys).all(|(x, y)| equal_with(x, y, compared))
+        }
+        (Expr::PVector(xs), Expr::PVector(ys)) => {
+            xs.len() == ys.len()
+                && xs
+                    .iter()
+                    .zip(ys.iter())
+                    .all(|(x, y)| equal_with(x, y, compared))
+        }
+        (Expr::Map(x), Expr::Map(y)) => {
+            x.len() == y.len()
+                && x.iter()
+                    .all(|(k, v)| y.get(k).is_some_and(|w| equal_with(v, w, compared)))
+        }
+        (Expr::Set(x), Expr::Set(y)) => x.len() == y.len() && x.iter().all(|k| y.contains(k)),
+        _ => is_eqv(a, b),
+    }
+}
+
+// Hashes consistent with the predicates above: whenever is_eqv(a, b) holds,
+// hash_eqv gives both the same hash (and likewise for is_equal/hash_equal).
+// Identity-compared values hash their address.
+pub fn hash_eqv<H: Hasher>(expr: &Expr, state: &mut H) {
+    hash_with(expr, state, false, 0)
+}
+
+pub fn hash_equal<H: Hasher>(expr: &Expr, state: &mut H) {
+    hash_with(expr, state, true, 0)
+}
+
+// How many vectors deep hash_equal looks. A vector can contain itself, so
+// hashing can't follow every element; values that are equal? unfold the same
+// way to any depth, so cutting off at a fixed one keeps their hashes equal.
+const HASH_DEPTH: usize = 4;
+
+// `depth` counts the vectors the value is inside
+fn hash_with<H: Hasher>(expr: &Expr, state: &mut H, structural: bool, depth: usize) {
+    std::mem::discriminant(expr).hash(state);
+    match expr {
+        Expr::Number(n) => n.hash(state),
+        Expr::Real(x) => x.to_bits().hash(state),
+        Expr::Bool(b) => b.hash(state),
+        Expr::Symbol(s) | Expr::Keyword(s) => s.hash(state),
+        Expr::Char(c) => c.hash(state),
+        Expr::Date(date) => date.hash(state),
+        Expr::Str(s) if structural => s.hash(state),
+        Expr::Str(s) => Rc::as_ptr(s).cast::<u8>().hash(state),
+        Expr::List(items) => {
+            items.len().hash(state);
+            for item in items {
+                hash_with(item, state, structural, depth);
+            }
+        }
+        Expr::Vector(items) if structural => {
+            let items = items.borrow();
+            items.len().hash(state);
+            if depth < HASH_DEPTH {
+                for item in items.iter() {
+                    hash_with(item, state, structural, depth + 1);
+                }
+            }
+        }
+        Expr::Vector(items) => Rc::as_ptr(items).hash(state),
+        Expr::Bytevector(bytes) if structural => bytes.borrow().hash(state),
+        Expr::Bytevector(bytes) => Rc::as_ptr(bytes).hash(state),
+        Expr::HashTable(table) => Rc::as_ptr(table).hash(state),
+        Expr::PVector(items) if structural => {
+            items.len().hash(state);
+            for item in items.iter() {
+                hash_with(item, state, structural, depth);
+            }
+        }
+        Expr::PVector(items) => items.as_ptr().hash(state),
+        // Iteration order depends on how a map or set was built, so entries
+        // are hashed separately and combined in an order-independent way
+        Expr::Map(map) if structural => {
+            map.len().hash(state);
+            let combined = map.iter().fold(0u64, |acc, (key, value)| {
+                let mut entry = DefaultHasher::new();
+                hash_with(key, &mut entry, true, depth);
+                hash_with(value, &mut entry, true, depth);
+                acc.wrapping_add(entry.finish())
+            });
+            combined.hash(state);
+        }
+        Expr::Map(map) => map.as_ptr().hash(state),
+        Expr::Set(set) if structural => {
+            set.len().hash(state);
+            let combined = set.iter().fold(0u64, |acc, item| {
+                let mut entry = DefaultHasher::new();
+                hash_with(item, &mut entry, true, depth);
+                acc.wrapping_add(entry.finish())
+            });
+            combined.hash(state);
+        }
+        Expr::Set(set) => set.as_ptr().hash(state),
+        Expr::Builtin(name) => name.hash(state),
+        Expr::Unspecified | Expr::Eof => {}
+        Expr::Error(message) => message.hash(state),
+        Expr::Lambda { body, .. } => Rc::as_ptr(body).hash(state),
+    }
+}
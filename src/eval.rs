@@ -1,298 +1,579 @@
+use crate::bytevectors;
+use crate::chars;
 use crate::env::Env;
-use crate::types::Expr;
+use crate::equality::{is_equal, is_eqv};
+use crate::format;
+use crate::hash_tables;
+use crate::persistent;
+use crate::random;
+use crate::readtable;
+use crate::reflection;
+use crate::strings;
+use crate::symbol::{self, Symbol};
+use crate::time;
+use crate::types::{Expr, KeywordParam};
+use crate::vectors;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::rc::Rc;
 
 pub fn eval(expr: &Expr, env: &mut Env) -> Result<Expr, String> {
     match expr {
-        Expr::Number(n) => Ok(Expr::Number(*n)),
-        Expr::Bool(b) => Ok(Expr::Bool(*b)),
-        Expr::Symbol(s) => {
-            let result = env.get(s);
-            match result {
-                Some(expr) => Ok(expr.clone()),
-                None => Err(format!("undefined variable: {}", s)),
-            }
+        Expr::Error(message) => Err(message.to_string()),
+        Expr::Symbol(s) => lookup(s, env),
+        Expr::List(items) => {
+            let result = eval_list(items, env);
+            env.note_result(items, &result);
+            result
+        }
+        // Everything else (numbers, strings, vectors, procedures, ...)
+        // evaluates to itself
+        _ => Ok(expr.clone()),
+    }
+}
+
+// Evaluates a list form: a special form or a procedure call. Each special form
+// has a function of its own, so nested forms only recurse through small stack
+// frames.
+fn eval_list(items: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    if items.is_empty() {
+        return Err("cannot eval empty list".to_string());
+    }
+
+    let func = &items[0];
+    // Special forms: These control when/if their arguments are evaluated
+    // They must be handled BEFORE evaluating arguments (unlike regular functions)
+    if let Expr::Symbol(name) = func {
+        match special_form(name) {
+            Some(SpecialForm::Define) => return eval_define(items, env),
+            Some(SpecialForm::Set) => return eval_set(items, env),
+            Some(SpecialForm::If) => return eval_if(items, env),
+            Some(SpecialForm::Quote) => return eval_quote(items),
+            Some(SpecialForm::Lambda) => return eval_lambda(items, env),
+            None => {}
+        }
+    }
+    eval_call(items, env)
+}
+
+// Evaluates a procedure call
+fn eval_call(items: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    // Regular functions: ALL arguments are evaluated first, then passed to the function
+    // This is done here (line below) BEFORE matching function names
+    // Example: (+ 1 (+ 2 3)) → evaluates 1 and (+ 2 3) first → (+ 1 5) → 6
+    let mut args = Vec::with_capacity(items.len() - 1);
+    for arg in &items[1..] {
+        args.push(eval(arg, env)?);
+    }
+
+    call(&items[0], args, env)
+}
+
+// The value of a variable, or the built-in procedure of that name
+fn lookup(s: &Symbol, env: &Env) -> Result<Expr, String> {
+    match env.get(s) {
+        Some(expr) => Ok(expr.clone()),
+        None => match builtin_name(s) {
+            Some(builtin) => Ok(Expr::Builtin(builtin)),
+            None => Err(format!("undefined variable: {}", s)),
+        },
+    }
+}
+
+// Calls the procedure named or produced by `func` with the evaluated `args`
+fn call(func: &Expr, args: Vec<Expr>, env: &mut Env) -> Result<Expr, String> {
+    // Check if func is a Symbol (built-in operator or variable holding a lambda)
+    // or if it needs evaluation (e.g., nested lambda call)
+    let func_evaled = if let Expr::Symbol(name) = func {
+        // Try to get from environment (might be a user-defined function)
+        if let Some(val) = env.get(name) {
+            val.clone()
+        } else if let Some(builtin) = builtin_name(name) {
+            Expr::Builtin(builtin)
+        } else {
+            return Err(format!("unknown function: {}", name));
+        }
+    } else {
+        // Not a symbol, evaluate it (e.g., ((lambda ...) args))
+        eval(func, env)?
+    };
+
+    apply(&func_evaled, args, env)
+}
+
+fn eval_define(items: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    // Special form: (define x 10)
+    // - x must NOT be evaluated (stays as symbol)
+    // - only the value (10) is evaluated
+    // - returns the unspecified value, so nothing is printed
+    if items.len() != 3 {
+        return Err("define requires 2 arguments".to_string());
+    }
+    let var_name = &items[1];
+    let mut value = eval(&items[2], env)?;
+    if let Expr::Symbol(var) = var_name {
+        // Name anonymous procedures after their first binding
+        if let Expr::Lambda {
+            name: name @ None, ..
+        } = &mut value
+        {
+            *name = Some(var.clone());
+        }
+        env.insert(var.clone(), value);
+        Ok(Expr::Unspecified)
+    } else {
+        Err("define requires a symbol as first argument".to_string())
+    }
+}
+
+fn eval_set(items: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    // Special form: (set! x 20)
+    // - like define, but x must already be bound
+    if items.len() != 3 {
+        return Err("set! requires 2 arguments".to_string());
+    }
+    let var = match &items[1] {
+        Expr::Symbol(var) => var,
+        _ => return Err("set! requires a symbol as first argument".to_string()),
+    };
+    if !env.contains_key(var) {
+        return Err(format!("set!: undefined variable: {}", var));
+    }
+    let value = eval(&items[2], env)?;
+    env.insert(var.clone(), value);
+    Ok(Expr::Unspecified)
+}
+
+fn eval_if(items: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    // Special form: (if condition then-branch else-branch)
+    // - Only evaluates the condition first
+    // - Then evaluates ONLY ONE branch (not both)
+    // - Example: (if false 1 (/ 1 0)) won't error because (/ 1 0) never runs
+    if items.len() != 4 {
+        return Err("if requires 3 arguments".to_string());
+    }
+    // Which values count as true depends on the dialect (see env::Dialect)
+    let condition = eval(&items[1], env)?;
+    let cond_bool = env
+        .dialect()
+        .is_truthy(&condition)
+        .map_err(|err| format!("if {}", err))?;
+    if cond_bool {
+        eval(&items[2], env)
+    } else {
+        eval(&items[3], env)
+    }
+}
+
+fn eval_quote(items: &[Expr]) -> Result<Expr, String> {
+    // Special form: (quote (1 2 3))
+    // - Returns its argument as data, without evaluating it
+    if items.len() != 2 {
+        return Err("quote requires exactly 1 argument".to_string());
+    }
+    Ok(items[1].clone())
+}
+
+fn eval_lambda(items: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    // Special form: (lambda (x y) (+ x y))
+    // or with named parameters: (lambda (x #:key (scale 1)) ...)
+    // - Parameters are NOT evaluated (stay as symbols)
+    // - Body is NOT evaluated yet (evaluated when function is called)
+    // - Captures the current environment (closure)
+    if items.len() != 3 {
+        return Err("lambda requires 2 arguments: params and body".to_string());
+    }
+
+    let (params, keywords) = match &items[1] {
+        Expr::List(param_list) => parse_params(param_list)?,
+        _ => return Err("lambda parameters must be a list".to_string()),
+    };
+
+    let body = Rc::new(items[2].clone());
+    let spans = env.copy_spans(&items[2], &body);
+    let closure_env = env.closure_copy();
+
+    Ok(Expr::Lambda {
+        params,
+        keywords,
+        body,
+        spans,
+        closure_env,
+        name: None,
+    })
+}
+
+// Built-in procedures implemented directly in this file
+const BUILTINS: &[&str] = &[
+    "+",
+    "-",
+    "*",
+    "/",
+    "<",
+    ">",
+    "<=",
+    ">=",
+    "=",
+    "eq?",
+    "eqv?",
+    "equal?",
+    "not",
+    "boolean=?",
+    "eof-object",
+    "eof-object?",
+];
+
+// The special forms, which decide when (and whether) their operands are
+// evaluated
+#[derive(Clone, Copy)]
+enum SpecialForm {
+    Define,
+    If,
+    Set,
+    Quote,
+    Lambda,
+}
+
+thread_local! {
+    // The special forms and built-in procedures by their interned names, so
+    // resolving the head of a form hashes only the symbol's address
+    static SPECIAL_FORMS: HashMap<Symbol, SpecialForm> = [
+        ("define", SpecialForm::Define),
+        ("set!", SpecialForm::Set),
+        ("if", SpecialForm::If),
+        ("quote", SpecialForm::Quote),
+        ("lambda", SpecialForm::Lambda),
+    ]
+    .into_iter()
+    .map(|(name, form)| (Symbol::intern(name), form))
+    .collect();
+    static BUILTIN_NAMES: HashMap<Symbol, &'static str> = [
+        BUILTINS,
+        strings::BUILTINS,
+        chars::BUILTINS,
+        vectors::BUILTINS,
+        bytevectors::BUILTINS,
+        hash_tables::BUILTINS,
+        symbol::BUILTINS,
+        reflection::BUILTINS,
+        persistent::BUILTINS,
+        time::BUILTINS,
+        random::BUILTINS,
+        format::BUILTINS,
+        readtable::BUILTINS,
+    ]
+    .iter()
+    .flat_map(|names| names.iter())
+    .map(|&name| (Symbol::intern(name), name))
+    .collect();
+}
+
+fn special_form(name: &Symbol) -> Option<SpecialForm> {
+    SPECIAL_FORMS.with(|forms| forms.get(name).copied())
+}
+
+// Looks up a built-in procedure by name, so it can be used as a value
+// (e.g. passed to vector-map) as well as called directly
+fn builtin_name(name: &Symbol) -> Option<&'static str> {
+    BUILTIN_NAMES.with(|names| names.get(name).copied())
+}
+
+// Splits a lambda parameter list into positional parameters and the keyword
+// parameters that follow #:key
+fn parse_params(param_list: &[Expr]) -> Result<(Vec<Symbol>, Vec<KeywordParam>), String> {
+    let mut params = Vec::new();
+    let mut keywords = Vec::new();
+    let mut after_key = false;
+    for param in param_list {
+        match param {
+            Expr::Keyword(marker) if marker == "key" && !after_key => after_key = true,
+            Expr::Symbol(name) if !after_key => params.push(name.clone()),
+            Expr::Symbol(name) => keywords.push(KeywordParam {
+                name: name.clone(),
+                default: None,
+            }),
+            Expr::List(spec) if after_key => match spec.as_slice() {
+                [Expr::Symbol(name), default] => keywords.push(KeywordParam {
+                    name: name.clone(),
+                    default: Some(default.clone()),
+                }),
+                _ => {
+                    return Err(format!(
+                        "keyword parameters must be a symbol or (symbol default), got {}",
+                        param.written()
+                    ));
+                }
+            },
+            _ => return Err("lambda parameters must be symbols".to_string()),
         }
+    }
+    Ok((params, keywords))
+}
+
+// Calls a procedure value (a lambda or a built-in) with already-evaluated arguments
+pub fn apply(func: &Expr, args: Vec<Expr>, env: &mut Env) -> Result<Expr, String> {
+    match func {
         Expr::Lambda {
             params,
+            keywords,
             body,
+            spans,
             closure_env,
-        } => Ok(Expr::Lambda {
-            params: params.clone(),
-            body: body.clone(),
-            closure_env: closure_env.clone(),
-        }),
+            ..
+        } => {
+            if keywords.is_empty() && params.len() != args.len() {
+                return Err(format!(
+                    "function expects {} arguments, got {}",
+                    params.len(),
+                    args.len()
+                ));
+            }
+            if args.len() < params.len() {
+                return Err(format!(
+                    "function expects {} arguments before its keyword arguments, got {}",
+                    params.len(),
+                    args.len()
+                ));
+            }
+            let mut args = args;
+            let keyword_args = args.split_off(params.len());
 
-        Expr::List(items) => {
-            if items.is_empty() {
-                return Err("cannot eval empty list".to_string());
+            // Create new environment: start with current env (for recursion),
+            // then add closure env (for lexical scoping), then params
+            let mut new_env = env.clone();
+            for (k, v) in closure_env.iter() {
+                new_env.insert(k.clone(), v.clone());
             }
 
-            let func = &items[0];
-            // Special forms: These control when/if their arguments are evaluated
-            // They must be handled BEFORE evaluating arguments (unlike regular functions)
-            if let Expr::Symbol(name) = func {
-                match name.as_str() {
-                    "define" => {
-                        // Special form: (define x 10)
-                        // - x must NOT be evaluated (stays as symbol)
-                        // - only the value (10) is evaluated
-                        let var_name = &items[1];
-                        let value = eval(&items[2], env)?;
-                        if let Expr::Symbol(var) = var_name {
-                            env.insert(var.clone(), value.clone());
-                            return Ok(value);
-                        } else {
-                            return Err("define requires a symbol as first argument".to_string());
-                        }
-                    }
-                    "if" => {
-                        // Special form: (if condition then-branch else-branch)
-                        // - Only evaluates the condition first
-                        // - Then evaluates ONLY ONE branch (not both)
-                        // - Example: (if false 1 (/ 1 0)) won't error because (/ 1 0) never runs
-                        if items.len() != 4 {
-                            return Err("if requires 3 arguments".to_string());
-                        }
-                        let condition = eval(&items[1], env)?;
-                        let cond_bool = match condition {
-                            Expr::Bool(b) => b,
-                            Expr::Number(n) => n != 0,
-                            _ => return Err("if condition must be a boolean".to_string()),
-                        };
-                        if cond_bool {
-                            return eval(&items[2], env);
-                        } else {
-                            return eval(&items[3], env);
-                        }
-                    }
-                    "lambda" => {
-                        // Special form: (lambda (x y) (+ x y))
-                        // - Parameters are NOT evaluated (stay as symbols)
-                        // - Body is NOT evaluated yet (evaluated when function is called)
-                        // - Captures the current environment (closure)
-                        if items.len() != 3 {
-                            return Err("lambda requires 2 arguments: params and body".to_string());
-                        }
-
-                        let params = match &items[1] {
-                            Expr::List(param_list) => {
-                                let mut params = Vec::new();
-                                for param in param_list {
-                                    if let Expr::Symbol(name) = param {
-                                        params.push(name.clone());
-                                    } else {
-                                        return Err("lambda parameters must be symbols".to_string());
-                                    }
-                                }
-                                params
-                            }
-                            _ => return Err("lambda parameters must be a list".to_string()),
-                        };
-
-                        let body = Box::new(items[2].clone());
-                        let closure_env = env.clone();
-
-                        return Ok(Expr::Lambda {
-                            params,
-                            body,
-                            closure_env,
-                        });
-                    }
-                    _ => {}
-                }
+            // Bind parameters to arguments
+            for (param, arg) in params.iter().zip(args) {
+                new_env.insert(param.clone(), arg);
             }
-            // Regular functions: ALL arguments are evaluated first, then passed to the function
-            // This is done here (line below) BEFORE matching function names
-            // Example: (+ 1 (+ 2 3)) → evaluates 1 and (+ 2 3) first → (+ 1 5) → 6
-            let args: Result<Vec<Expr>, String> =
-                items[1..].iter().map(|arg| eval(arg, env)).collect();
-
-            let args = args?;
-
-            // Check if func is a Symbol (built-in operator or variable holding a lambda)
-            // or if it needs evaluation (e.g., nested lambda call)
-            let func_evaled = if let Expr::Symbol(name) = func {
-                // Try to get from environment (might be a user-defined function)
-                if let Some(val) = env.get(name) {
-                    val.clone()
-                } else {
-                    // It's a built-in function, keep as symbol
-                    func.clone()
-                }
-            } else {
-                // Not a symbol, evaluate it (e.g., ((lambda ...) args))
-                eval(func, env)?
-            };
+            new_env.set_spans(spans.clone());
+            bind_keywords(keywords, keyword_args, &mut new_env)?;
 
-            // Check if it's a user-defined function (lambda)
-            if let Expr::Lambda {
-                params,
-                body,
-                closure_env,
-            } = func_evaled
-            {
-                if params.len() != args.len() {
-                    return Err(format!(
-                        "function expects {} arguments, got {}",
-                        params.len(),
-                        args.len()
-                    ));
-                }
+            // Evaluate body in the new environment
+            eval(body, &mut new_env)
+        }
+        Expr::Builtin(name) => call_builtin(name, &args, env),
+        _ => Err(format!("not a procedure: {}", func.written())),
+    }
+}
 
-                // Create new environment: start with current env (for recursion),
-                // then add closure env (for lexical scoping), then params
-                let mut new_env = env.clone();
-                for (k, v) in closure_env.iter() {
-                    new_env.insert(k.clone(), v.clone());
-                }
+// Binds `:name value` arguments to keyword parameters. Defaults for the
+// keywords left out are evaluated in the new environment, so they can refer
+// to the positional parameters and to earlier keyword parameters.
+fn bind_keywords(keywords: &[KeywordParam], args: Vec<Expr>, env: &mut Env) -> Result<(), String> {
+    let mut given: Vec<Option<Expr>> = vec![None; keywords.len()];
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let Expr::Keyword(key) = &arg else {
+            return Err(format!(
+                "expected a keyword argument, got {}",
+                arg.written()
+            ));
+        };
+        let Some(i) = keywords.iter().position(|k| k.name == *key) else {
+            return Err(format!("unknown keyword argument :{}", key));
+        };
+        let Some(value) = args.next() else {
+            return Err(format!("missing value for keyword argument :{}", key));
+        };
+        if given[i].is_some() {
+            return Err(format!("keyword argument :{} given more than once", key));
+        }
+        given[i] = Some(value);
+    }
+    for (param, value) in keywords.iter().zip(given) {
+        let value = match (value, &param.default) {
+            (Some(value), _) => value,
+            (None, Some(default)) => eval(default, env)?,
+            (None, None) => {
+                return Err(format!("missing keyword argument :{}", param.name));
+            }
+        };
+        env.insert(param.name.clone(), value);
+    }
+    Ok(())
+}
 
-                // Bind parameters to arguments
-                for (param, arg) in params.iter().zip(args.iter()) {
-                    new_env.insert(param.clone(), arg.clone());
-                }
+fn integer_overflow() -> String {
+    "integer overflow".to_string()
+}
 
-                // Evaluate body in the new environment
-                return eval(&body, &mut new_env);
+fn call_builtin(func_name: &str, args: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    match func_name {
+        "+" => match numeric_args("+", args)? {
+            Numbers::Integers(nums) => nums
+                .iter()
+                .try_fold(0i64, |sum, &n| sum.checked_add(n))
+                .map(Expr::Number)
+                .ok_or_else(integer_overflow),
+            Numbers::Reals(nums) => Ok(Expr::Real(nums.iter().sum())),
+        },
+        "-" => {
+            if args.is_empty() {
+                return Err("- requires at least 1 number".to_string());
             }
+            match numeric_args("-", args)? {
+                Numbers::Integers(nums) if nums.len() == 1 => nums[0]
+                    .checked_neg()
+                    .map(Expr::Number)
+                    .ok_or_else(integer_overflow),
+                Numbers::Integers(nums) => nums[1..]
+                    .iter()
+                    .try_fold(nums[0], |difference, &n| difference.checked_sub(n))
+                    .map(Expr::Number)
+                    .ok_or_else(integer_overflow),
+                Numbers::Reals(nums) if nums.len() == 1 => Ok(Expr::Real(-nums[0])),
+                Numbers::Reals(nums) => Ok(Expr::Real(nums[0] - nums[1..].iter().sum::<f64>())),
+            }
+        }
 
-            if let Expr::Symbol(func_name) = func {
-                match func_name.as_str() {
-                    "+" => {
-                        let sum = args
-                            .iter()
-                            .map(|e| match e {
-                                Expr::Number(n) => Ok(*n),
-                                _ => Err("+ requires numbers".to_string()),
-                            })
-                            .collect::<Result<Vec<i32>, String>>()?
-                            .iter()
-                            .sum();
-                        Ok(Expr::Number(sum))
-                    }
-                    "-" => {
-                        let nums = args
-                            .iter()
-                            .map(|e| match e {
-                                Expr::Number(n) => Ok(*n),
-                                _ => Err("- requires numbers".to_string()),
-                            })
-                            .collect::<Result<Vec<i32>, String>>()?;
-                        if nums.len() == 1 {
-                            return Ok(Expr::Number((-1) * nums[0]));
-                        }
-                        let mut ans = nums[0] * 2;
-                        for num in nums {
-                            ans = ans - num;
-                        }
-                        return Ok(Expr::Number(ans));
-                    }
-
-                    "*" => {
-                        let nums = args
-                            .iter()
-                            .map(|e| match e {
-                                Expr::Number(n) => Ok(*n),
-                                _ => Err("* requires numbers".to_string()),
-                            })
-                            .collect::<Result<Vec<i32>, String>>()?;
-                        if nums.len() < 2 {
-                            return Err("* requires at least 2 numbers".to_string());
-                        }
-                        let answer = nums.iter().fold(1, |acc, v| acc * v);
-                        return Ok(Expr::Number(answer));
-                    }
+        "*" => {
+            if args.len() < 2 {
+                return Err("* requires at least 2 numbers".to_string());
+            }
+            match numeric_args("*", args)? {
+                Numbers::Integers(nums) => nums
+                    .iter()
+                    .try_fold(1i64, |product, &n| product.checked_mul(n))
+                    .map(Expr::Number)
+                    .ok_or_else(integer_overflow),
+                Numbers::Reals(nums) => Ok(Expr::Real(nums.iter().product())),
+            }
+        }
 
-                    "/" => {
-                        let nums = args
-                            .iter()
-                            .map(|e| match e {
-                                Expr::Number(n) => Ok(*n),
-                                _ => Err("/ requires numbers".to_string()),
-                            })
-                            .collect::<Result<Vec<i32>, String>>()?;
-                        let v0 = nums.get(0).ok_or("/ requires 2 numbers".to_string())?;
-
-                        let v1 = nums.get(1).ok_or("/ requires 2 numbers".to_string())?;
-                        if *v1 == 0 {
-                            return Err("cannot divide by 0".to_string());
-                        }
-                        return Ok(Expr::Number(v0 / v1));
+        "/" => {
+            if args.len() < 2 {
+                return Err("/ requires 2 numbers".to_string());
+            }
+            match numeric_args("/", args)? {
+                Numbers::Integers(nums) => {
+                    if nums[1] == 0 {
+                        return Err("cannot divide by 0".to_string());
                     }
+                    // i64::MIN / -1 is the one quotient that does not fit
+                    nums[0]
+                        .checked_div(nums[1])
+                        .map(Expr::Number)
+                        .ok_or_else(integer_overflow)
+                }
+                Numbers::Reals(nums) => Ok(Expr::Real(nums[0] / nums[1])),
+            }
+        }
 
-                    ">" => {
-                        if args.len() != 2 {
-                            return Err("> requires exactly 2 arguments".to_string());
-                        }
-                        let nums = args
-                            .iter()
-                            .map(|e| match e {
-                                Expr::Number(n) => Ok(*n),
-                                _ => Err("> requires numbers".to_string()),
-                            })
-                            .collect::<Result<Vec<i32>, String>>()?;
-                        return Ok(Expr::Bool(nums[0] > nums[1]));
-                    }
+        "<" => compare_numbers("<", args, Ordering::is_lt),
+        ">" => compare_numbers(">", args, Ordering::is_gt),
+        "<=" => compare_numbers("<=", args, Ordering::is_le),
+        ">=" => compare_numbers(">=", args, Ordering::is_ge),
+        "=" => compare_numbers("=", args, Ordering::is_eq),
 
-                    "<" => {
-                        if args.len() != 2 {
-                            return Err("< requires exactly 2 arguments".to_string());
-                        }
-                        let nums = args
-                            .iter()
-                            .map(|e| match e {
-                                Expr::Number(n) => Ok(*n),
-                                _ => Err("< requires numbers".to_string()),
-                            })
-                            .collect::<Result<Vec<i32>, String>>()?;
-                        return Ok(Expr::Bool(nums[0] < nums[1]));
-                    }
+        "eq?" | "eqv?" | "equal?" => {
+            if args.len() != 2 {
+                return Err(format!("{} requires exactly 2 arguments", func_name));
+            }
+            let same = if func_name == "equal?" {
+                is_equal(&args[0], &args[1])
+            } else {
+                is_eqv(&args[0], &args[1])
+            };
+            Ok(Expr::Bool(same))
+        }
 
-                    "<=" => {
-                        if args.len() != 2 {
-                            return Err("<= requires exactly 2 arguments".to_string());
-                        }
-                        let nums = args
-                            .iter()
-                            .map(|e| match e {
-                                Expr::Number(n) => Ok(*n),
-                                _ => Err("<= requires numbers".to_string()),
-                            })
-                            .collect::<Result<Vec<i32>, String>>()?;
-                        return Ok(Expr::Bool(nums[0] <= nums[1]));
-                    }
+        "not" => {
+            if args.len() != 1 {
+                return Err("not requires exactly 1 argument".to_string());
+            }
+            let truthy = env
+                .dialect()
+                .is_truthy(&args[0])
+                .map_err(|err| format!("not {}", err))?;
+            Ok(Expr::Bool(!truthy))
+        }
 
-                    ">=" => {
-                        if args.len() != 2 {
-                            return Err(">= requires exactly 2 arguments".to_string());
-                        }
-                        let nums = args
-                            .iter()
-                            .map(|e| match e {
-                                Expr::Number(n) => Ok(*n),
-                                _ => Err(">= requires numbers".to_string()),
-                            })
-                            .collect::<Result<Vec<i32>, String>>()?;
-                        return Ok(Expr::Bool(nums[0] >= nums[1]));
-                    }
+        "boolean=?" => {
+            if args.len() < 2 {
+                return Err("boolean=? requires at least 2 arguments".to_string());
+            }
+            let bools = args
+                .iter()
+                .map(|e| match e {
+                    Expr::Bool(b) => Ok(*b),
+                    _ => Err("boolean=? requires booleans".to_string()),
+                })
+                .collect::<Result<Vec<bool>, String>>()?;
+            Ok(Expr::Bool(bools.windows(2).all(|w| w[0] == w[1])))
+        }
+        "eof-object" => {
+            if !args.is_empty() {
+                return Err("eof-object takes no arguments".to_string());
+            }
+            Ok(Expr::Eof)
+        }
 
-                    "=" => {
-                        if args.len() != 2 {
-                            return Err("= requires exactly 2 arguments".to_string());
-                        }
-                        let nums = args
-                            .iter()
-                            .map(|e| match e {
-                                Expr::Number(n) => Ok(*n),
-                                _ => Err("= requires numbers".to_string()),
-                            })
-                            .collect::<Result<Vec<i32>, String>>()?;
-                        return Ok(Expr::Bool(nums[0] == nums[1]));
-                    }
-                    _ => Err(format!("unknown function: {}", func_name)),
-                }
-            } else {
-                Err("first element must be a function name".to_string())
+        "eof-object?" => {
+            if args.len() != 1 {
+                return Err("eof-object? requires exactly 1 argument".to_string());
             }
+            Ok(Expr::Bool(args[0] == Expr::Eof))
         }
+
+        _ => strings::call(func_name, args)
+            .or_else(|| chars::call(func_name, args))
+            .or_else(|| vectors::call(func_name, args, env))
+            .or_else(|| bytevectors::call(func_name, args))
+            .or_else(|| hash_tables::call(func_name, args, env))
+            .or_else(|| symbol::call(func_name, args))
+            .or_else(|| reflection::call(func_name, args))
+            .or_else(|| persistent::call(func_name, args))
+            .or_else(|| time::call(func_name, args))
+            .or_else(|| random::call(func_name, args, env))
+            .or_else(|| format::call(func_name, args))
+            .or_else(|| readtable::call(func_name, args, env))
+            .unwrap_or_else(|| Err(format!("unknown function: {}", func_name))),
+    }
+}
+
+// The arguments of an arithmetic builtin. Integers stay exact unless some
+// argument is a real, in which case they are all converted to reals.
+enum Numbers {
+    Integers(Vec<i64>),
+    Reals(Vec<f64>),
+}
+
+fn numeric_args(name: &str, args: &[Expr]) -> Result<Numbers, String> {
+    if args.iter().any(|e| matches!(e, Expr::Real(_))) {
+        args.iter()
+            .map(|e| match e {
+                Expr::Number(n) => Ok(*n as f64),
+                Expr::Real(x) => Ok(*x),
+                _ => Err(format!("{} requires numbers", name)),
+            })
+            .collect::<Result<Vec<f64>, String>>()
+            .map(Numbers::Reals)
+    } else {
+        args.iter()
+            .map(|e| match e {
+                Expr::Number(n) => Ok(*n),
+                _ => Err(format!("{} requires numbers", name)),
+            })
+            .collect::<Result<Vec<i64>, String>>()
+            .map(Numbers::Integers)
+    }
+}
+
+// Chained numeric comparison: (< a b c) holds when every adjacent pair does.
+// Comparisons involving NaN are always false.
+fn compare_numbers(name: &str, args: &[Expr], test: fn(Ordering) -> bool) -> Result<Expr, String> {
+    if args.len() < 2 {
+        return Err(format!("{} requires at least 2 arguments", name));
     }
+    let holds = match numeric_args(name, args)? {
+        Numbers::Integers(nums) => nums.windows(2).all(|w| test(w[0].cmp(&w[1]))),
+        Numbers::Reals(nums) => nums
+            .windows(2)
+            .all(|w| w[0].partial_cmp(&w[1]).is_some_and(test)),
+    };
+    Ok(Expr::Bool(holds))
 }
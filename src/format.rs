@@ -0,0 +1,156 @@
+use crate::strings::{expect_args, format_radix};
+use crate::types::{Expr, format_real};
+
+pub const BUILTINS: &[&str] = &["format", "display", "write"];
+
+// Built-in output procedures. Returns None when `name` is not one of them.
+//
+// (format control arg ...) and (format #f control arg ...) return the
+// formatted string; (format #t control arg ...) writes it to standard output.
+// (display obj) prints a value as ~a does and (write obj) as ~s does, with no
+// newline after it.
+pub fn call(name: &str, args: &[Expr]) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "format" => format(args),
+        "display" => expect_args(name, args, 1).map(|_| {
+            print!("{}", args[0]);
+            Expr::Unspecified
+        }),
+        "write" => expect_args(name, args, 1).map(|_| {
+            print!("{}", args[0].written());
+            Expr::Unspecified
+        }),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn format(args: &[Expr]) -> Result<Expr, String> {
+    let (to_stdout, rest) = match args {
+        [Expr::Bool(dest), rest @ ..] => (*dest, rest),
+        _ => (false, args),
+    };
+    let (control, values) = match rest {
+        [Expr::Str(control), values @ ..] => (control, values),
+        [other, ..] => {
+            return Err(format!(
+                "format requires a control string, got {}",
+                other.written()
+            ));
+        }
+        [] => return Err("format requires a control string".to_string()),
+    };
+    let text = format_directives(control, values)?;
+    if to_stdout {
+        print!("{}", text);
+        Ok(Expr::Unspecified)
+    } else {
+        Ok(Expr::Str(text.into()))
+    }
+}
+
+// The widest field a directive may ask for, so a mistyped width is an error
+// rather than an enormous string
+const MAX_WIDTH: usize = 10_000;
+
+// Expands the directives in `control`, consuming one value for each of
+// ~a ~s ~d ~x ~o ~b. A directive may start with a minimum width and a pad
+// character, as in ~8a or ~5,'0d; text directives pad on the right, numbers
+// on the left, and the @ modifier (~8@a) swaps the side.
+fn format_directives(control: &str, values: &[Expr]) -> Result<String, String> {
+    let mut out = String::new();
+    let mut values = values.iter();
+    let mut chars = control.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '~' {
+            out.push(c);
+            continue;
+        }
+
+        let mut width: usize = 0;
+        while let Some(digit) = chars.peek().and_then(|d| d.to_digit(10)) {
+            width = width
+                .checked_mul(10)
+                .and_then(|width| width.checked_add(digit as usize))
+                .filter(|&width| width <= MAX_WIDTH)
+                .ok_or_else(|| {
+                    format!("format: width is more than the maximum of {}", MAX_WIDTH)
+                })?;
+            chars.next();
+        }
+        let mut pad = ' ';
+        if chars.peek() == Some(&',') {
+            chars.next();
+            if chars.next() != Some('\'') {
+                return Err("format: expected 'c after , in a directive".to_string());
+            }
+            pad = chars
+                .next()
+                .ok_or("format: control string ends inside a directive")?;
+        }
+        let swap_side = chars.peek() == Some(&'@');
+        if swap_side {
+            chars.next();
+        }
+
+        let directive = chars.next().ok_or("format: control string ends with ~")?;
+        let (text, pad_left) = match directive.to_ascii_lowercase() {
+            '%' => ("\n".to_string(), false),
+            '~' => ("~".to_string(), false),
+            'a' | 's' | 'd' | 'x' | 'o' | 'b' => {
+                let value = values
+                    .next()
+                    .ok_or_else(|| format!("format: not enough arguments for ~{}", directive))?;
+                match directive.to_ascii_lowercase() {
+                    'a' => (value.to_string(), false),
+                    's' => (value.written().to_string(), false),
+                    'd' => (number_text(directive, value, 10)?, true),
+                    'x' => (number_text(directive, value, 16)?, true),
+                    'o' => (number_text(directive, value, 8)?, true),
+                    _ => (number_text(directive, value, 2)?, true),
+                }
+            }
+            other => return Err(format!("format: unknown directive ~{}", other)),
+        };
+        pad_to(&mut out, &text, width, pad, pad_left != swap_side);
+    }
+    if values.next().is_some() {
+        return Err("format: too many arguments for the control string".to_string());
+    }
+    Ok(out)
+}
+
+fn number_text(directive: char, value: &Expr, radix: u32) -> Result<String, String> {
+    match value {
+        Expr::Number(n) => Ok(format_radix(*n, radix)),
+        Expr::Real(x) if radix == 10 => Ok(format_real(*x)),
+        other => Err(format!(
+            "format: ~{} requires {}, got {}",
+            directive,
+            if radix == 10 {
+                "a number"
+            } else {
+                "an integer"
+            },
+            other.written()
+        )),
+    }
+}
+
+// Appends `text` padded to `width` characters. Zero-padding a negative
+// number keeps the sign in front: -0042.
+fn pad_to(out: &mut String, text: &str, width: usize, pad: char, pad_left: bool) {
+    let fill = width.saturating_sub(text.chars().count());
+    if !pad_left {
+        out.push_str(text);
+        out.extend(std::iter::repeat_n(pad, fill));
+        return;
+    }
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(digits) if pad == '0' => ("-", digits),
+        _ => ("", text),
+    };
+    out.push_str(sign);
+    out.extend(std::iter::repeat_n(pad, fill));
+    out.push_str(digits);
+}
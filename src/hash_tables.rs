@@ -0,0 +1,336 @@
+use crate::env::Env;
+use crate::equality::{hash_equal, hash_eqv, is_equal, is_eqv};
+use crate::eval::apply;
+use crate::strings::expect_args;
+use crate::types::Expr;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::rc::Rc;
+
+pub const BUILTINS: &[&str] = &[
+    "make-hash-table",
+    "alist->hash-table",
+    "hash-table?",
+    "hash-table-ref",
+    "hash-table-ref/default",
+    "hash-table-set!",
+    "hash-table-delete!",
+    "hash-table-contains?",
+    "hash-table-count",
+    "hash-table-keys",
+    "hash-table-values",
+    "hash-table->alist",
+    "hash-table-update!",
+    "hash-table-walk",
+];
+
+// How a hash table compares its keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyEquality {
+    // eq? / eqv?: strings, vectors, tables and procedures match only themselves
+    Eqv,
+    // equal?: strings, lists and vectors match by content
+    Equal,
+}
+
+// A mutable hash table keyed by arbitrary Exprs.
+// Entries are kept in a Vec so iteration order is deterministic (insertion
+// order, except that deleting an entry moves the last entry into its place);
+// `index` maps each key hash to the positions of the entries with that hash.
+// Keys should not be mutated while they are in an equal? table.
+#[derive(Debug, PartialEq)]
+pub struct HashTable {
+    equality: KeyEquality,
+    entries: Vec<(Expr, Expr)>,
+    index: HashMap<u64, Vec<usize>>,
+}
+
+impl HashTable {
+    pub fn new(equality: KeyEquality) -> Self {
+        HashTable {
+            equality,
+            entries: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn equality(&self) -> KeyEquality {
+        self.equality
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[(Expr, Expr)] {
+        &self.entries
+    }
+
+    pub fn get(&self, key: &Expr) -> Option<&Expr> {
+        self.position(key).map(|i| &self.entries[i].1)
+    }
+
+    // Returns the previous value for `key`, if any
+    pub fn insert(&mut self, key: Expr, value: Expr) -> Option<Expr> {
+        if let Some(i) = self.position(&key) {
+            return Some(std::mem::replace(&mut self.entries[i].1, value));
+        }
+        let hash = self.hash(&key);
+        self.index.entry(hash).or_default().push(self.entries.len());
+        self.entries.push((key, value));
+        None
+    }
+
+    pub fn remove(&mut self, key: &Expr) -> Option<Expr> {
+        let i = self.position(key)?;
+        self.unindex(i);
+        let last = self.entries.len() - 1;
+        if i != last {
+            // The last entry is about to move into slot i
+            self.unindex(last);
+            let hash = self.hash(&self.entries[last].0);
+            self.index.entry(hash).or_default().push(i);
+        }
+        Some(self.entries.swap_remove(i).1)
+    }
+
+    fn hash(&self, key: &Expr) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        match self.equality {
+            KeyEquality::Eqv => hash_eqv(key, &mut hasher),
+            KeyEquality::Equal => hash_equal(key, &mut hasher),
+        }
+        hasher.finish()
+    }
+
+    fn same_key(&self, a: &Expr, b: &Expr) -> bool {
+        match self.equality {
+            KeyEquality::Eqv => is_eqv(a, b),
+            KeyEquality::Equal => is_equal(a, b),
+        }
+    }
+
+    fn position(&self, key: &Expr) -> Option<usize> {
+        self.index
+            .get(&self.hash(key))?
+            .iter()
+            .copied()
+            .find(|&i| self.same_key(&self.entries[i].0, key))
+    }
+
+    fn unindex(&mut self, i: usize) {
+        let hash = self.hash(&self.entries[i].0);
+        if let Some(bucket) = self.index.get_mut(&hash) {
+            bucket.retain(|&j| j != i);
+            if bucket.is_empty() {
+                self.index.remove(&hash);
+            }
+        }
+    }
+}
+
+// Built-in hash table procedures. Returns None when `name` is not one of them.
+pub fn call(name: &str, args: &[Expr], env: &mut Env) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "make-hash-table" => make_hash_table(args),
+        "alist->hash-table" => alist_to_hash_table(args),
+        "hash-table?" => {
+            expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::HashTable(_))))
+        }
+        "hash-table-ref" => hash_table_ref(name, args, false),
+        "hash-table-ref/default" => hash_table_ref(name, args, true),
+        "hash-table-set!" => hash_table_set(args),
+        "hash-table-delete!" => hash_table_delete(args),
+        "hash-table-contains?" => hash_table_contains(args),
+        "hash-table-count" => {
+            table_arg_only(name, args).map(|t| Expr::Number(t.borrow().len() as i64))
+        }
+        "hash-table-keys" => table_arg_only(name, args).map(|t| {
+            Expr::List(
+                t.borrow()
+                    .entries()
+                    .iter()
+                    .map(|(k, _)| k.clone())
+                    .collect(),
+            )
+        }),
+        "hash-table-values" => table_arg_only(name, args).map(|t| {
+            Expr::List(
+                t.borrow()
+                    .entries()
+                    .iter()
+                    .map(|(_, v)| v.clone())
+                    .collect(),
+            )
+        }),
+        "hash-table->alist" => table_arg_only(name, args).map(|t| {
+            Expr::List(
+                t.borrow()
+                    .entries()
+                    .iter()
+                    .map(|(k, v)| Expr::List(vec![k.clone(), v.clone()]))
+                    .collect(),
+            )
+        }),
+        "hash-table-update!" => hash_table_update(args, env),
+        "hash-table-walk" => hash_table_walk(args, env),
+        _ => return None,
+    };
+    Some(result)
+}
+
+pub fn new_hash_table(table: HashTable) -> Expr {
+    Expr::HashTable(Rc::new(RefCell::new(table)))
+}
+
+fn table_arg<'a>(name: &str, expr: &'a Expr) -> Result<&'a Rc<RefCell<HashTable>>, String> {
+    match expr {
+        Expr::HashTable(table) => Ok(table),
+        _ => Err(format!(
+            "{} requires a hash table, got {}",
+            name,
+            expr.written()
+        )),
+    }
+}
+
+fn table_arg_only<'a>(name: &str, args: &'a [Expr]) -> Result<&'a Rc<RefCell<HashTable>>, String> {
+    expect_args(name, args, 1)?;
+    table_arg(name, &args[0])
+}
+
+// The optional equality argument: one of the procedures eq?, eqv? or equal?
+fn equality_arg(name: &str, expr: Option<&Expr>) -> Result<KeyEquality, String> {
+    match expr {
+        None | Some(Expr::Builtin("equal?")) => Ok(KeyEquality::Equal),
+        Some(Expr::Builtin("eq?")) | Some(Expr::Builtin("eqv?")) => Ok(KeyEquality::Eqv),
+        Some(other) => Err(format!(
+            "{} requires eq?, eqv? or equal? as key equality, got {}",
+            name,
+            other.written()
+        )),
+    }
+}
+
+fn make_hash_table(args: &[Expr]) -> Result<Expr, String> {
+    if args.len() > 1 {
+        return Err("make-hash-table takes at most 1 argument".to_string());
+    }
+    let equality = equality_arg("make-hash-table", args.first())?;
+    Ok(new_hash_table(HashTable::new(equality)))
+}
+
+// (alist->hash-table '((k1 v1) (k2 v2)) [equality]). When a key appears more
+// than once, the first entry wins, as in SRFI 69 and as assoc would find it.
+fn alist_to_hash_table(args: &[Expr]) -> Result<Expr, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err("alist->hash-table requires 1 or 2 arguments".to_string());
+    }
+    let mut table = HashTable::new(equality_arg("alist->hash-table", args.get(1))?);
+    let alist = match &args[0] {
+        Expr::List(items) => items,
+        other => {
+            return Err(format!(
+                "alist->hash-table requires a list, got {}",
+                other.written()
+            ));
+        }
+    };
+    for entry in alist {
+        match entry {
+            Expr::List(pair) if pair.len() == 2 => {
+                if table.get(&pair[0]).is_none() {
+                    table.insert(pair[0].clone(), pair[1].clone());
+                }
+            }
+            other => {
+                return Err(format!(
+                    "alist->hash-table entries must be (key value) lists, got {}",
+                    other.written()
+                ));
+            }
+        }
+    }
+    Ok(new_hash_table(table))
+}
+
+fn hash_table_ref(name: &str, args: &[Expr], default_required: bool) -> Result<Expr, String> {
+    let valid = if default_required {
+        args.len() == 3
+    } else {
+        args.len() == 2 || args.len() == 3
+    };
+    if !valid {
+        let expected = if default_required { "3" } else { "2 or 3" };
+        return Err(format!("{} requires {} arguments", name, expected));
+    }
+    let table = table_arg(name, &args[0])?.borrow();
+    match (table.get(&args[1]), args.get(2)) {
+        (Some(value), _) => Ok(value.clone()),
+        (None, Some(default)) => Ok(default.clone()),
+        (None, None) => Err(format!("{}: key not found: {}", name, args[1].written())),
+    }
+}
+
+fn hash_table_set(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("hash-table-set!", args, 3)?;
+    let table = table_arg("hash-table-set!", &args[0])?;
+    table.borrow_mut().insert(args[1].clone(), args[2].clone());
+    Ok(Expr::Unspecified)
+}
+
+fn hash_table_delete(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("hash-table-delete!", args, 2)?;
+    let table = table_arg("hash-table-delete!", &args[0])?;
+    table.borrow_mut().remove(&args[1]);
+    Ok(Expr::Unspecified)
+}
+
+fn hash_table_contains(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("hash-table-contains?", args, 2)?;
+    let table = table_arg("hash-table-contains?", &args[0])?.borrow();
+    Ok(Expr::Bool(table.get(&args[1]).is_some()))
+}
+
+// (hash-table-update! table key proc [default]): stores (proc current-value),
+// where a missing key starts from `default`
+fn hash_table_update(args: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    if args.len() != 3 && args.len() != 4 {
+        return Err("hash-table-update! requires 3 or 4 arguments".to_string());
+    }
+    let table = table_arg("hash-table-update!", &args[0])?;
+    // Release the borrow before calling proc, which may use the table itself
+    let current = table.borrow().get(&args[1]).cloned();
+    let current = match (current, args.get(3)) {
+        (Some(value), _) => value,
+        (None, Some(default)) => default.clone(),
+        (None, None) => {
+            return Err(format!(
+                "hash-table-update!: key not found: {}",
+                args[1].written()
+            ));
+        }
+    };
+    let updated = apply(&args[2], vec![current], env)?;
+    table.borrow_mut().insert(args[1].clone(), updated);
+    Ok(Expr::Unspecified)
+}
+
+// (hash-table-walk table proc): calls (proc key value) for every entry
+fn hash_table_walk(args: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    expect_args("hash-table-walk", args, 2)?;
+    let entries = table_arg("hash-table-walk", &args[0])?
+        .borrow()
+        .entries()
+        .to_vec();
+    for (key, value) in entries {
+        apply(&args[1], vec![key, value], env)?;
+    }
+    Ok(Expr::Unspecified)
+}
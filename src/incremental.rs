@@ -0,0 +1,241 @@
+use crate::parser::{DEFAULT_MAX_DEPTH, ParseError, Parser};
+use crate::readtable::Readtable;
+use crate::source_map::SourceMap;
+use crate::tokenizer::{Lexer, Span, Token, TokenKind};
+use crate::types::Expr;
+
+// A parser fed text a chunk at a time, as a REPL or an editor receives it.
+// Iterating yields each top-level form once all of it has arrived; when the
+// rest of the input is only the start of a form, iteration pauses (returns
+// None) until more text is pushed.
+//
+//     let mut reader = IncrementalParser::new();
+//     reader.push("(define x\n");
+//     assert!(reader.next().is_none() && reader.depth() == 1);
+//     reader.push("  42)\n");
+//     let (form, span) = reader.next().unwrap()?;
+#[derive(Debug, Clone)]
+pub struct IncrementalParser {
+    // The text from `offset` on; what comes before has been read and dropped
+    buffer: String,
+    offset: usize,
+    // Where the unread text starts: byte offset (from the start of all the
+    // input, like every span returned), line and column
+    position: Span,
+    // Set by `finish`: no more text is coming
+    finished: bool,
+    // What the last scan found waiting in the unread text
+    depth: usize,
+    incomplete: bool,
+    source_map: SourceMap,
+    readtable: Readtable,
+    max_depth: usize,
+}
+
+impl Default for IncrementalParser {
+    fn default() -> Self {
+        IncrementalParser::new()
+    }
+}
+
+// How far the unread text goes towards a complete form
+enum Pending {
+    // The tokens up to and including this index make a form
+    Form(usize),
+    // A ) with no list to close
+    StrayClose(usize),
+    // The text so far starts a form but does not finish it
+    Incomplete { depth: usize },
+    // Nothing but whitespace and comments, the last a #; still waiting for
+    // its datum
+    Comment { span: Span, depth: usize },
+    // Nothing but whitespace and comments
+    Empty,
+}
+
+impl IncrementalParser {
+    pub fn new() -> IncrementalParser {
+        IncrementalParser {
+            buffer: String::new(),
+            offset: 0,
+            position: Span {
+                start: 0,
+                end: 0,
+                line: 1,
+                column: 1,
+            },
+            finished: false,
+            depth: 0,
+            incomplete: false,
+            source_map: SourceMap::new(),
+            readtable: Readtable::default(),
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    // How deeply a form may nest before it is an error
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    // Reads the forms not yet returned with the reader macros in `readtable`
+    pub fn set_readtable(&mut self, readtable: Readtable) {
+        self.readtable = readtable;
+    }
+
+    // Appends the next chunk of input. Chunks can split the text anywhere,
+    // even inside a token. The text of the forms already returned is
+    // dropped first, so the buffer only holds what is still to be read.
+    pub fn push(&mut self, chunk: &str) {
+        let consumed = self.position.start - self.offset;
+        if consumed > 0 {
+            self.buffer.drain(..consumed);
+            self.offset += consumed;
+        }
+        self.buffer.push_str(chunk);
+        self.finished = false;
+    }
+
+    // Marks the end of the input. Atoms at the very end then count as
+    // complete, and anything left unfinished is reported as an error.
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    // How many lists (or vectors) the unread text opens without closing
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    // Whether the unread text starts a form that needs more input: an open
+    // list, string or block comment, or a ' or #; with nothing after it yet
+    pub fn is_incomplete(&self) -> bool {
+        self.incomplete
+    }
+
+    // The text not yet returned as forms
+    pub fn pending(&self) -> &str {
+        &self.buffer[self.position.start - self.offset..]
+    }
+
+    // The spans of the lists in the forms returned so far
+    pub fn take_source_map(&mut self) -> SourceMap {
+        std::mem::take(&mut self.source_map)
+    }
+
+    // A span in the buffer as a span in all the input pushed so far
+    fn at(&self, mut span: Span) -> Span {
+        span.start += self.offset;
+        span.end += self.offset;
+        span
+    }
+
+    // Reads tokens into `tokens` until it is clear what the unread text
+    // holds, so only the next form is lexed rather than the whole buffer
+    fn pending_form<'a>(&self, lexer: &mut Lexer<'a, '_>, tokens: &mut Vec<Token<'a>>) -> Pending {
+        let mut depth = 0;
+        for token in lexer.by_ref() {
+            let token = Token {
+                span: self.at(token.span),
+                ..token
+            };
+            let i = tokens.len();
+            tokens.push(token);
+            if token.is_unterminated() {
+                return Pending::Incomplete { depth };
+            }
+            match token.kind {
+                TokenKind::LParen | TokenKind::VectorOpen | TokenKind::BytevectorOpen => depth += 1,
+                TokenKind::RParen if depth == 0 => return Pending::StrayClose(i),
+                TokenKind::RParen => depth -= 1,
+                TokenKind::Quote => continue,
+                TokenKind::Atom if self.readtable.contains(token.text) => continue,
+                _ => {}
+            }
+            // An atom running up to the end of the text may continue in
+            // the next chunk, as 12 may become 123
+            let may_grow = !self.finished
+                && token.span.end == self.offset + self.buffer.len()
+                && !matches!(token.kind, TokenKind::RParen | TokenKind::String);
+            if depth == 0 {
+                return if may_grow {
+                    Pending::Incomplete { depth }
+                } else {
+                    Pending::Form(i)
+                };
+            }
+        }
+        match lexer.unfinished_comment() {
+            Some((span, comment_depth)) if tokens.is_empty() => Pending::Comment {
+                span: self.at(span),
+                depth: comment_depth,
+            },
+            Some((_, comment_depth)) => Pending::Incomplete {
+                depth: depth + comment_depth,
+            },
+            None if tokens.is_empty() => Pending::Empty,
+            None => Pending::Incomplete { depth },
+        }
+    }
+}
+
+impl Iterator for IncrementalParser {
+    type Item = Result<(Expr, Span), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = Span {
+            start: self.position.start - self.offset,
+            end: self.position.start - self.offset,
+            ..self.position
+        };
+        let mut lexer = Lexer::starting_at(&self.buffer, start).with_readtable(&self.readtable);
+        let mut tokens = Vec::new();
+        let (depth, incomplete) = match self.pending_form(&mut lexer, &mut tokens) {
+            Pending::Form(last) => {
+                let mut parser = Parser::new(tokens[..=last].iter().copied())
+                    .with_readtable(&self.readtable)
+                    .with_max_depth(self.max_depth);
+                let result = parser.parse_value();
+                self.source_map.extend(parser.take_source_map());
+                self.position = tokens[last].end_position();
+                self.depth = 0;
+                self.incomplete = false;
+                return Some(result);
+            }
+            Pending::StrayClose(i) => {
+                self.position = tokens[i].end_position();
+                return Some(Err(ParseError {
+                    message: "unexpected ')' with no list to close".to_string(),
+                    span: tokens[i].span,
+                }));
+            }
+            Pending::Incomplete { depth } => (depth, true),
+            // No more input is coming, so the #; has nothing to comment out
+            Pending::Comment { span, .. } if self.finished => {
+                self.position = self.at(lexer.position());
+                self.depth = 0;
+                self.incomplete = false;
+                return Some(Err(ParseError {
+                    message: "missing datum after #;".to_string(),
+                    span,
+                }));
+            }
+            Pending::Comment { depth, .. } => (depth, true),
+            Pending::Empty => (0, false),
+        };
+        if incomplete && self.finished {
+            // No more input is coming, so let the parser say what is missing
+            let mut parser = Parser::new(tokens.iter().copied())
+                .with_readtable(&self.readtable)
+                .with_max_depth(self.max_depth);
+            let result = parser.parse_value();
+            self.position = tokens[tokens.len() - 1].end_position();
+            self.depth = 0;
+            self.incomplete = false;
+            return Some(result);
+        }
+        self.depth = depth;
+        self.incomplete = incomplete;
+        None
+    }
+}
@@ -1,11 +1,47 @@
+mod bytevectors;
+mod chars;
+mod cst;
 mod env;
+mod equality;
 mod eval;
+mod format;
+mod hash_tables;
+mod incremental;
+mod numbers;
 mod parser;
+mod persistent;
+mod persistent_map;
+mod persistent_vector;
+mod random;
+mod readtable;
+mod recovery;
+mod reflection;
+mod source_map;
+mod strings;
+mod symbol;
+mod time;
 mod tokenizer;
 mod types;
+mod vectors;
 
-pub use env::{Env, default_env};
-pub use eval::eval;
-pub use parser::parse;
-pub use tokenizer::tokenize;
-pub use types::Expr;
+pub use cst::{SyntaxNode, SyntaxTree, parse_cst, parse_cst_with};
+pub use env::{Dialect, Env, default_env};
+pub use eval::{apply, eval};
+pub use hash_tables::{HashTable, KeyEquality};
+pub use incremental::IncrementalParser;
+pub use parser::{
+    DEFAULT_MAX_DEPTH, ParseError, Parser, Program, parse, parse_all, parse_program,
+    parse_program_with, parse_spanned,
+};
+pub use persistent_map::{PersistentMap, PersistentSet};
+pub use persistent_vector::PersistentVector;
+pub use readtable::{ReaderMacro, Readtable};
+pub use recovery::{parse_recovering, parse_recovering_with};
+pub use source_map::SourceMap;
+pub use symbol::Symbol;
+pub use time::{Date, DateFields};
+pub use tokenizer::{
+    Lexer, Span, Token, TokenKind, Trivia, TriviaKind, read_char_literal, read_string_literal,
+    tokenize, tokenize_slices, tokenize_spanned,
+};
+pub use types::{Expr, KeywordParam, Written};
@@ -5,13 +5,46 @@ use std::process;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
+    let usage = format!(
+        "Usage: {} [--dialect c|scheme] [--seed N] <file_path>",
+        args[0]
+    );
 
-    if args.len() != 2 {
-        eprintln!("Usage: {} <file_path>", args[0]);
-        process::exit(1);
+    let mut dialect = Dialect::default();
+    let mut seed = None;
+    let mut file_path = None;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        if arg == "--dialect" {
+            dialect = match rest.next().map(|s| s.as_str()) {
+                Some("c") => Dialect::CLike,
+                Some("scheme") => Dialect::Scheme,
+                _ => {
+                    eprintln!("{}", usage);
+                    process::exit(1);
+                }
+            };
+        } else if arg == "--seed" {
+            // Seeds the random number generator, for reproducible runs
+            seed = match rest.next().map(|s| s.parse::<u64>()) {
+                Some(Ok(n)) => Some(n),
+                _ => {
+                    eprintln!("{}", usage);
+                    process::exit(1);
+                }
+            };
+        } else if file_path.is_none() {
+            file_path = Some(arg);
+        } else {
+            eprintln!("{}", usage);
+            process::exit(1);
+        }
     }
 
-    let file_path = &args[1];
+    let Some(file_path) = file_path else {
+        eprintln!("{}", usage);
+        process::exit(1);
+    };
 
     // Read the file
     let contents = fs::read_to_string(file_path).unwrap_or_else(|err| {
@@ -20,43 +53,47 @@ fn main() {
     });
 
     // Create environment
-    let mut env = default_env();
-
-    // Process each line/expression
-    for (line_num, line) in contents.lines().enumerate() {
-        let line = line.trim();
-
-        // Skip empty lines and comments
-        if line.is_empty() || line.starts_with(';') {
-            continue;
-        }
-
-        // Tokenize
-        let tokens = tokenize(line);
-        if tokens.is_empty() {
-            continue;
-        }
+    let mut env = Env::with_dialect(dialect);
+    if let Some(seed) = seed {
+        env.set_random_seed(seed);
+    }
 
-        // Parse
-        let token_refs: Vec<&str> = tokens.iter().map(|s| s.as_str()).collect();
-        let expr = match parse(&token_refs) {
-            Ok(e) => e,
-            Err(err) => {
-                eprintln!("Parse error at line {}: {}", line_num + 1, err);
-                process::exit(1);
-            }
+    // Read and evaluate each top-level form in order. Each form is read with
+    // the readtable as the forms before it left it, so reader macros they
+    // define apply to the rest of the file. That means a form can only be
+    // read once the forms before it have run, so a malformed form stops the
+    // program at that point.
+    let mut reader = IncrementalParser::new();
+    reader.push(&contents);
+    reader.finish();
+    loop {
+        reader.set_readtable(env.readtable());
+        let Some(parsed) = reader.next() else {
+            break;
         };
+        let (expr, form_span) = parsed.unwrap_or_else(|err| {
+            eprintln!("Parse error at {}", err);
+            process::exit(1);
+        });
+        env.set_source_map(reader.take_source_map());
 
-        // Evaluate
         let result = match eval(&expr, &mut env) {
             Ok(r) => r,
             Err(err) => {
-                eprintln!("Eval error at line {}: {}", line_num + 1, err);
+                // Point at the innermost form that failed, when it is known
+                let span = env.take_error_span().unwrap_or(form_span);
+                eprintln!(
+                    "Eval error at line {}, column {}: {}",
+                    span.line, span.column, err
+                );
                 process::exit(1);
             }
         };
 
-        // Print result
-        println!("{}", result);
+        // Print result (strings are shown quoted, as `write` would print them)
+        // Forms evaluated for their effect, like define, print nothing
+        if result != Expr::Unspecified {
+            println!("{}", result.written());
+        }
     }
 }
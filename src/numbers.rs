@@ -0,0 +1,152 @@
+use crate::types::Expr;
+
+// Reads a numeric literal in the R7RS syntax:
+// - an optional radix prefix (#x #b #o #d) and exactness prefix (#e #i), in
+//   either order
+// - then a signed integer in that radix, a ratio such as 3/4, a decimal
+//   such as 1.5, .5e-3 or 1e3 (radix 10 only), or +inf.0, -inf.0 or +nan.0
+// There are no rationals or bignums, so a ratio that is not a whole number
+// reads as a real, and #e is an error unless the value is a whole number that
+// fits in an integer. An integer too big for 64 bits is an error in any radix.
+// Complex numbers are not supported.
+//
+// Returns None when `text` is not a number at all (so it is a symbol), and
+// an error when it starts like a number but isn't one, as 12abc does.
+pub(crate) fn parse_number(text: &str) -> Option<Result<Expr, String>> {
+    let mut radix = None;
+    let mut exact = None;
+    let mut repeated = false;
+    let mut body = text;
+    while let Some(rest) = body.strip_prefix('#') {
+        let mut chars = rest.chars();
+        let flag = chars.next().map(|c| c.to_ascii_lowercase());
+        match flag {
+            Some('x' | 'b' | 'o' | 'd') => {
+                repeated |= radix.is_some();
+                radix = Some(match flag {
+                    Some('x') => 16,
+                    Some('b') => 2,
+                    Some('o') => 8,
+                    _ => 10,
+                });
+            }
+            Some('e' | 'i') => {
+                repeated |= exact.is_some();
+                exact = Some(flag == Some('e'));
+            }
+            // #t, #\a and the like are not numbers
+            _ => return None,
+        }
+        body = chars.as_str();
+    }
+    let radix = radix.unwrap_or(10);
+    // Nor are atoms such as #date or #xml, where the prefixes are followed
+    // by something other than a number. A prefix on its own is malformed.
+    let prefix_only = body.is_empty() && body.len() < text.len();
+    if !looks_numeric(body, radix) && !prefix_only {
+        return None;
+    }
+    if repeated {
+        return Some(Err(malformed(text)));
+    }
+    let value = parse_real(body, radix, text);
+    Some(value.and_then(|value| {
+        match (exact, &value) {
+            (Some(false), Expr::Number(n)) => Ok(Expr::Real(*n as f64)),
+            (Some(true), Expr::Real(x)) => exact_integer(*x)
+                .map(Expr::Number)
+                .ok_or_else(|| format!("{} has no exact integer value", text)),
+            _ => Ok(value),
+        }
+    }))
+}
+
+fn malformed(text: &str) -> String {
+    format!("malformed number: {}", text)
+}
+
+// Whether the text after any prefixes starts the way a number does: with a
+// digit of `radix`, or a sign or point before one. +inf.0 and friends count
+// too. Atoms such as +, - and ... are symbols.
+fn looks_numeric(text: &str, radix: u32) -> bool {
+    let unsigned = text.strip_prefix(['+', '-']);
+    if let Some(special) = unsigned
+        && matches!(special, "inf.0" | "nan.0")
+    {
+        return true;
+    }
+    let digits = unsigned.unwrap_or(text);
+    let digits = digits.strip_prefix('.').unwrap_or(digits);
+    digits.starts_with(|c: char| c.is_digit(radix))
+}
+
+// A signed integer, ratio or decimal, as an integer when it is exact
+fn parse_real(body: &str, radix: u32, text: &str) -> Result<Expr, String> {
+    match body {
+        "+inf.0" => return Ok(Expr::Real(f64::INFINITY)),
+        "-inf.0" => return Ok(Expr::Real(f64::NEG_INFINITY)),
+        "+nan.0" | "-nan.0" => return Ok(Expr::Real(f64::NAN)),
+        _ => {}
+    }
+    let unsigned = body.strip_prefix(['+', '-']).unwrap_or(body);
+    if let Some((numerator, denominator)) = body.split_once('/') {
+        let unsigned_numerator = numerator.strip_prefix(['+', '-']).unwrap_or(numerator);
+        if !is_uinteger(unsigned_numerator, radix) || !is_uinteger(denominator, radix) {
+            return Err(malformed(text));
+        }
+        let numerator = parse_integer(numerator, radix, text)?;
+        let denominator = parse_integer(denominator, radix, text)?;
+        if denominator == 0 {
+            return Err(malformed(text));
+        }
+        return Ok(if numerator % denominator == 0 {
+            Expr::Number(numerator / denominator)
+        } else {
+            Expr::Real(numerator as f64 / denominator as f64)
+        });
+    }
+    if is_uinteger(unsigned, radix) {
+        return parse_integer(body, radix, text).map(Expr::Number);
+    }
+    if radix == 10 && is_decimal(unsigned) {
+        // The grammar has been checked, so only the value is left to work out
+        return body
+            .parse::<f64>()
+            .map(Expr::Real)
+            .map_err(|_| malformed(text));
+    }
+    Err(malformed(text))
+}
+
+// Whether `digits` is one or more digits of `radix`, with no sign
+fn is_uinteger(digits: &str, radix: u32) -> bool {
+    !digits.is_empty() && digits.chars().all(|c| c.is_digit(radix))
+}
+
+// Digits after an optional sign, read together with the sign so that
+// i64::MIN fits
+fn parse_integer(digits: &str, radix: u32, text: &str) -> Result<i64, String> {
+    i64::from_str_radix(digits, radix).map_err(|_| format!("integer out of range: {}", text))
+}
+
+// An unsigned decimal: digits with at most one point (and at least one digit
+// in all), then optionally e, a sign and more digits
+fn is_decimal(text: &str) -> bool {
+    let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (text, None),
+    };
+    let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let mantissa_ok = all_digits(whole) && all_digits(fraction) && whole.len() + fraction.len() > 0;
+    let exponent_ok = exponent.is_none_or(|exponent| {
+        let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        !digits.is_empty() && all_digits(digits)
+    });
+    mantissa_ok && exponent_ok
+}
+
+// The integer equal to `x`, if there is one that fits
+fn exact_integer(x: f64) -> Option<i64> {
+    (x.fract() == 0.0 && x >= i64::MIN as f64 && x < i64::MAX as f64).then_some(x as i64)
+}
@@ -1,57 +1,344 @@
+use crate::numbers::parse_number;
+use crate::readtable::Readtable;
+use crate::source_map::SourceMap;
+use crate::tokenizer::{
+    Lexer, Span, Token, TokenKind, kind_of, lex_error, read_char_literal, read_string_literal,
+};
 use crate::types::Expr;
+use std::borrow::Cow;
+use std::fmt;
 
-pub fn parse(tokens: &Vec<&str>) -> Result<Expr, String> {
-    if tokens.is_empty() {
-        return Err("no tokens to parse".to_string());
-    }
-    let mut cursor = 0;
-    let result = parse_value(tokens, &mut cursor)?;
-    if cursor < tokens.len() {
-        return Err(format!(
-            "unexpected token '{}' after expression",
-            tokens[cursor]
-        ));
-    }
-    Ok(result)
-}
-
-fn parse_value(tokens: &Vec<&str>, cursor: &mut usize) -> Result<Expr, String> {
-    let current = *tokens
-        .get(*cursor)
-        .ok_or("unexpected end of input".to_string())?;
-
-    if current == "(" {
-        parse_list(tokens, cursor)
-    } else {
-        *cursor += 1;
-        match current.parse::<i32>() {
-            Ok(num) => Ok(Expr::Number(num)),
-            Err(_) => match current.parse::<bool>() {
-                Ok(c) => Ok(Expr::Bool(c)),
-                Err(_) => Ok(Expr::Symbol(current.to_string())),
-            },
-        }
+// A parse error and the part of the source it is about
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.span.line, self.span.column, self.message
+        )
     }
 }
 
-fn parse_list(tokens: &Vec<&str>, cursor: &mut usize) -> Result<Expr, String> {
-    let mut list: Vec<Expr> = Vec::new();
-    *cursor += 1;
-    loop {
-        match tokens.get(*cursor) {
-            Some(v) => {
-                if *v == ")" {
-                    break;
-                } else {
-                    let value = parse_value(tokens, cursor)?;
-                    list.push(value);
+pub fn parse(tokens: &[&str]) -> Result<Expr, String> {
+    parse_one(&mut Parser::new(unspanned(tokens)))
+        .map(|(expr, _)| expr)
+        .map_err(|err| err.message)
+}
+
+// A parsed program: its top-level forms, where each of them is, and the
+// spans of the lists inside them
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Program {
+    pub forms: Vec<Expr>,
+    pub spans: Vec<Span>,
+    pub source_map: SourceMap,
+}
+
+// Parses every expression in `tokens`, in order; no tokens is an empty program
+pub fn parse_all(tokens: &[&str]) -> Result<Vec<Expr>, String> {
+    parse_tokens(unspanned(tokens), &Readtable::default())
+        .map(|program| program.forms)
+        .map_err(|err| err.message)
+}
+
+// Reads all the top-level forms of a program, however they are laid out
+// across lines
+pub fn parse_program(source: &str) -> Result<Program, ParseError> {
+    parse_program_with(source, &Readtable::default())
+}
+
+// Reads a program with the reader macros in `readtable`
+pub fn parse_program_with(source: &str, readtable: &Readtable) -> Result<Program, ParseError> {
+    parse_tokens(Lexer::new(source).with_readtable(readtable), readtable)
+}
+
+// Bare strings carry no positions, so errors are reported without them
+fn unspanned<'t, 'a>(tokens: &'t [&'a str]) -> impl Iterator<Item = Token<'a>> + 't {
+    tokens.iter().map(|text| Token {
+        kind: kind_of(text),
+        text,
+        span: Span::default(),
+    })
+}
+
+pub(crate) fn parse_tokens<'a>(
+    tokens: impl Iterator<Item = Token<'a>>,
+    readtable: &Readtable,
+) -> Result<Program, ParseError> {
+    let mut parser = Parser::new(tokens).with_readtable(readtable);
+    let mut program = Program::default();
+    for parsed in parser.by_ref() {
+        let (expr, span) = parsed?;
+        program.forms.push(expr);
+        program.spans.push(span);
+    }
+    program.source_map = parser.take_source_map();
+    Ok(program)
+}
+
+// Parses exactly one expression from tokens made by `tokenize_spanned`,
+// along with the spans of the lists in it
+pub fn parse_spanned(tokens: &[Token]) -> Result<(Expr, SourceMap), ParseError> {
+    let mut parser = Parser::new(tokens.iter().copied());
+    let (result, _) = parse_one(&mut parser)?;
+    Ok((result, parser.take_source_map()))
+}
+
+// The only expression in the parser's tokens
+fn parse_one<'a>(
+    parser: &mut Parser<'_, 'a, impl Iterator<Item = Token<'a>>>,
+) -> Result<(Expr, Span), ParseError> {
+    if parser.peek().is_none() {
+        return Err(ParseError {
+            message: "no tokens to parse".to_string(),
+            span: Span::default(),
+        });
+    }
+    let parsed = parser.parse_value()?;
+    if let Some(extra) = parser.peek() {
+        return Err(ParseError {
+            message: format!("unexpected token '{}' after expression", extra.text),
+            span: extra.span,
+        });
+    }
+    Ok(parsed)
+}
+
+// How deeply forms may nest unless a parser is told otherwise. Evaluating,
+// printing, comparing and dropping an expression all recurse once per level;
+// in a debug build, on the 2 MiB stack of a spawned thread, that runs out at
+// about 1150 levels, so this leaves room to spare.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
+// Reads expressions straight from a stream of tokens, such as a `Lexer`, one
+// top-level form at a time, without collecting the tokens first. Iteration
+// stops after the first error.
+//
+//     let mut forms = Parser::new(Lexer::new("(define x 1) x"));
+//     let (form, span) = forms.next().unwrap()?;
+pub struct Parser<'t, 'a, I: Iterator<Item = Token<'a>>> {
+    tokens: I,
+    peeked: Option<Token<'a>>,
+    // Just past the last token taken, for errors about missing tokens
+    last_end: Span,
+    failed: bool,
+    readtable: Cow<'t, Readtable>,
+    max_depth: usize,
+    source_map: SourceMap,
+}
+
+// A list or reader macro still waiting for the rest of its input
+enum Open<'a> {
+    List { start: Token<'a>, items: Vec<Expr> },
+    Macro { start: Token<'a> },
+}
+
+impl<'t, 'a, I: Iterator<Item = Token<'a>>> Parser<'t, 'a, I> {
+    pub fn new(tokens: I) -> Self {
+        Parser {
+            tokens,
+            peeked: None,
+            last_end: Span::default(),
+            failed: false,
+            readtable: Cow::Owned(Readtable::default()),
+            max_depth: DEFAULT_MAX_DEPTH,
+            source_map: SourceMap::new(),
+        }
+    }
+
+    // How deeply lists, vectors and quotes may nest in one form; deeper input
+    // is an error rather than a risk to the stack when the form is used
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    // Expands reader macros with `readtable` rather than the default table.
+    // The tokens should come from a lexer using the same table.
+    pub fn with_readtable(mut self, readtable: &'t Readtable) -> Self {
+        self.readtable = Cow::Borrowed(readtable);
+        self
+    }
+
+    // The spans of the lists read so far, leaving an empty map behind
+    pub fn take_source_map(&mut self) -> SourceMap {
+        std::mem::take(&mut self.source_map)
+    }
+
+    pub(crate) fn peek(&mut self) -> Option<Token<'a>> {
+        if self.peeked.is_none() {
+            self.peeked = self.tokens.next();
+        }
+        self.peeked
+    }
+
+    fn advance(&mut self) {
+        if let Some(token) = self.peek() {
+            self.peeked = None;
+            self.last_end = token.end_position();
+        }
+    }
+
+    // Parses one value, returning it with its span. Nesting is tracked on a
+    // stack of its own rather than by recursion, so deep input can't
+    // overflow the call stack; past `max_depth` levels it is an error.
+    pub(crate) fn parse_value(&mut self) -> Result<(Expr, Span), ParseError> {
+        let mut open: Vec<Open> = Vec::new();
+        loop {
+            let Some(current) = self.peek() else {
+                return Err(match open.last() {
+                    Some(Open::List { start, .. }) => ParseError {
+                        message: "unclosed list: missing ')'".to_string(),
+                        span: start.span,
+                    },
+                    _ => ParseError {
+                        message: "unexpected end of input".to_string(),
+                        span: self.last_end,
+                    },
+                });
+            };
+            let error = |message: String| ParseError {
+                message,
+                span: current.span,
+            };
+            self.advance();
+
+            let mut value = match current.kind {
+                TokenKind::LParen | TokenKind::VectorOpen | TokenKind::BytevectorOpen => {
+                    self.check_depth(open.len(), current)?;
+                    open.push(Open::List {
+                        start: current,
+                        items: Vec::new(),
+                    });
+                    continue;
+                }
+                // 'datum, or a dispatch macro such as #date, before the datum
+                // it reads
+                TokenKind::Quote => {
+                    self.check_depth(open.len(), current)?;
+                    open.push(Open::Macro { start: current });
+                    continue;
+                }
+                TokenKind::Atom if self.readtable.contains(current.text) => {
+                    self.check_depth(open.len(), current)?;
+                    open.push(Open::Macro { start: current });
+                    continue;
+                }
+                TokenKind::RParen => match open.pop() {
+                    Some(Open::List { start, items }) => {
+                        let span = Span {
+                            end: current.span.end,
+                            ..start.span
+                        };
+                        if start.kind == TokenKind::LParen {
+                            self.source_map.record(&items, span);
+                            (Expr::List(items), span)
+                        } else {
+                            // #( and #u8( are reader macros handed the items
+                            // as a list; vector elements are not evaluated
+                            (self.expand(start.text, Expr::List(items), span)?, span)
+                        }
+                    }
+                    Some(Open::Macro { start }) => {
+                        return Err(ParseError {
+                            message: format!("missing datum after {}", start.text),
+                            span: start.span,
+                        });
+                    }
+                    None => return Err(error("unexpected ')' with no list to close".to_string())),
+                },
+                TokenKind::String => {
+                    let text = read_string_literal(current.text).map_err(error)?;
+                    (Expr::Str(text.into()), current.span)
+                }
+                TokenKind::Char => {
+                    let c = read_char_literal(current.text).map_err(error)?;
+                    (Expr::Char(c), current.span)
+                }
+                TokenKind::Error => return Err(error(lex_error(current.text))),
+                TokenKind::Atom => (parse_atom(current.text).map_err(error)?, current.span),
+            };
+
+            // Hand the finished value to whatever is waiting for it
+            loop {
+                match open.pop() {
+                    None => return Ok(value),
+                    Some(Open::List { start, mut items }) => {
+                        items.push(value.0);
+                        open.push(Open::List { start, items });
+                        break;
+                    }
+                    Some(Open::Macro { start }) => {
+                        let span = Span {
+                            end: value.1.end,
+                            ..start.span
+                        };
+                        value = (self.expand(start.text, value.0, span)?, span);
+                    }
                 }
             }
+        }
+    }
+
+    fn check_depth(&self, depth: usize, opening: Token) -> Result<(), ParseError> {
+        if depth < self.max_depth {
+            return Ok(());
+        }
+        Err(ParseError {
+            message: format!("nesting too deep: more than {} levels", self.max_depth),
+            span: opening.span,
+        })
+    }
+
+    // Runs the reader macro `name` on what it read; the value is what the
+    // source between them reads as
+    fn expand(&mut self, name: &str, datum: Expr, span: Span) -> Result<Expr, ParseError> {
+        let value = match self.readtable.expand(name, datum) {
+            Some(result) => result.map_err(|message| ParseError { message, span })?,
             None => {
-                return Err("unclosed list: missing ')'".to_string());
+                return Err(ParseError {
+                    message: format!("unknown reader syntax {}", name),
+                    span,
+                });
             }
+        };
+        if let Expr::List(items) = &value {
+            self.source_map.record(items, span);
+        }
+        Ok(value)
+    }
+}
+
+impl<'a, I: Iterator<Item = Token<'a>>> Iterator for Parser<'_, 'a, I> {
+    type Item = Result<(Expr, Span), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
         }
+        self.peek()?;
+        let parsed = self.parse_value();
+        self.failed = parsed.is_err();
+        Some(parsed)
+    }
+}
+
+pub(crate) fn parse_atom(current: &str) -> Result<Expr, String> {
+    if let Some(number) = parse_number(current) {
+        return number;
+    }
+    match current {
+        "true" | "#t" | "#true" => Ok(Expr::Bool(true)),
+        "false" | "#f" | "#false" => Ok(Expr::Bool(false)),
+        _ => match current.strip_prefix("#:").or(current.strip_prefix(':')) {
+            Some(name) if !name.is_empty() => Ok(Expr::Keyword(name.into())),
+            _ => Ok(Expr::Symbol(current.into())),
+        },
     }
-    *cursor += 1;
-    Ok(Expr::List(list))
 }
@@ -0,0 +1,308 @@
+use crate::persistent_map::{PersistentMap, PersistentSet};
+use crate::strings::expect_args;
+use crate::types::Expr;
+
+pub const BUILTINS: &[&str] = &[
+    "hash-map",
+    "hash-set",
+    "pvector",
+    "hash-map?",
+    "hash-set?",
+    "pvector?",
+    "get",
+    "assoc",
+    "dissoc",
+    "conj",
+    "disj",
+    "pop",
+    "contains?",
+    "count",
+    "keys",
+    "vals",
+    "alist->hash-map",
+    "hash-map->alist",
+    "list->hash-set",
+    "hash-set->list",
+    "list->pvector",
+    "pvector->list",
+];
+
+// Built-in procedures on persistent maps, sets and vectors. Returns None when
+// `name` is not one of them. None of them modify their arguments: assoc, conj
+// and friends return a new collection sharing most of its structure with the
+// old one, which stays unchanged.
+pub fn call(name: &str, args: &[Expr]) -> Option<Result<Expr, String>> {
+    let result =
+        match name {
+            "hash-map" => pairs_into(name, &PersistentMap::new(), args).map(Expr::Map),
+            "hash-set" => Ok(Expr::Set(
+                args.iter()
+                    .fold(PersistentSet::new(), |set, item| set.insert(item.clone())),
+            )),
+            "pvector" => Ok(Expr::PVector(args.iter().cloned().collect())),
+            "hash-map?" => {
+                expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::Map(_))))
+            }
+            "hash-set?" => {
+                expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::Set(_))))
+            }
+            "pvector?" => {
+                expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::PVector(_))))
+            }
+            "get" => get(args),
+            "assoc" => assoc(args),
+            "dissoc" => dissoc(args),
+            "conj" => conj(args),
+            "disj" => disj(args),
+            "pop" => pop(args),
+            "contains?" => contains(args),
+            "count" => count(args),
+            "keys" => map_arg_only(name, args)
+                .map(|m| Expr::List(m.iter().map(|(k, _)| k.clone()).collect())),
+            "vals" => map_arg_only(name, args)
+                .map(|m| Expr::List(m.iter().map(|(_, v)| v.clone()).collect())),
+            "alist->hash-map" => alist_to_hash_map(args),
+            "hash-map->alist" => map_arg_only(name, args).map(|m| {
+                Expr::List(
+                    m.iter()
+                        .map(|(k, v)| Expr::List(vec![k.clone(), v.clone()]))
+                        .collect(),
+                )
+            }),
+            "list->hash-set" => list_arg_only(name, args).map(|items| {
+                Expr::Set(
+                    items
+                        .iter()
+                        .fold(PersistentSet::new(), |set, item| set.insert(item.clone())),
+                )
+            }),
+            "hash-set->list" => match args {
+                [Expr::Set(set)] => Ok(Expr::List(set.iter().cloned().collect())),
+                _ => expect_args(name, args, 1)
+                    .and_then(|_| Err(type_error(name, "a hash set", &args[0]))),
+            },
+            "list->pvector" => list_arg_only(name, args)
+                .map(|items| Expr::PVector(items.iter().cloned().collect())),
+            "pvector->list" => match args {
+                [Expr::PVector(v)] => Ok(Expr::List(v.iter().cloned().collect())),
+                _ => expect_args(name, args, 1)
+                    .and_then(|_| Err(type_error(name, "a pvector", &args[0]))),
+            },
+            _ => return None,
+        };
+    Some(result)
+}
+
+fn type_error(name: &str, expected: &str, got: &Expr) -> String {
+    format!("{} requires {}, got {}", name, expected, got.written())
+}
+
+fn map_arg_only<'a>(name: &str, args: &'a [Expr]) -> Result<&'a PersistentMap, String> {
+    expect_args(name, args, 1)?;
+    match &args[0] {
+        Expr::Map(map) => Ok(map),
+        other => Err(type_error(name, "a hash map", other)),
+    }
+}
+
+fn list_arg_only<'a>(name: &str, args: &'a [Expr]) -> Result<&'a [Expr], String> {
+    expect_args(name, args, 1)?;
+    match &args[0] {
+        Expr::List(items) => Ok(items),
+        other => Err(type_error(name, "a list", other)),
+    }
+}
+
+// Converts an index argument; out-of-range indexes are left to the caller,
+// since get, assoc and contains? each treat them differently
+fn index_arg(name: &str, expr: &Expr) -> Result<usize, String> {
+    match expr {
+        Expr::Number(n) if *n >= 0 => Ok(*n as usize),
+        other => Err(type_error(name, "a non-negative integer index", other)),
+    }
+}
+
+// Adds alternating keys and values to `map`
+fn pairs_into(name: &str, map: &PersistentMap, args: &[Expr]) -> Result<PersistentMap, String> {
+    if !args.len().is_multiple_of(2) {
+        return Err(format!("{} requires keys and values in pairs", name));
+    }
+    Ok(args.chunks(2).fold(map.clone(), |map, pair| {
+        map.insert(pair[0].clone(), pair[1].clone())
+    }))
+}
+
+// (get coll key [default]): the value for a map key, the item at a pvector
+// index, or the set member equal? to key; default (#f if omitted) otherwise
+fn get(args: &[Expr]) -> Result<Expr, String> {
+    if args.len() != 2 && args.len() != 3 {
+        return Err("get requires 2 or 3 arguments".to_string());
+    }
+    let found = match &args[0] {
+        Expr::Map(map) => map.get(&args[1]),
+        Expr::Set(set) => set.get(&args[1]),
+        Expr::PVector(v) => v.get(index_arg("get", &args[1])?),
+        other => return Err(type_error("get", "a hash map, hash set or pvector", other)),
+    };
+    Ok(found.or(args.get(2)).cloned().unwrap_or(Expr::Bool(false)))
+}
+
+// (assoc coll key value ...): a map with the keys bound, or a pvector with
+// the indexes replaced (an index equal to the length appends)
+fn assoc(args: &[Expr]) -> Result<Expr, String> {
+    if args.len() < 3 {
+        return Err("assoc requires at least 3 arguments".to_string());
+    }
+    match &args[0] {
+        Expr::Map(map) => pairs_into("assoc", map, &args[1..]).map(Expr::Map),
+        Expr::PVector(v) => {
+            if args.len() % 2 != 1 {
+                return Err("assoc requires keys and values in pairs".to_string());
+            }
+            let mut v = v.clone();
+            for pair in args[1..].chunks(2) {
+                let i = index_arg("assoc", &pair[0])?;
+                v = match v.set(i, pair[1].clone()) {
+                    Some(updated) => updated,
+                    None if i == v.len() => v.push(pair[1].clone()),
+                    None => {
+                        return Err(format!(
+                            "assoc: index {} out of range for pvector of length {}",
+                            i,
+                            v.len()
+                        ));
+                    }
+                };
+            }
+            Ok(Expr::PVector(v))
+        }
+        other => Err(type_error("assoc", "a hash map or pvector", other)),
+    }
+}
+
+// (dissoc map key ...): a map without the keys
+fn dissoc(args: &[Expr]) -> Result<Expr, String> {
+    match args {
+        [Expr::Map(map), keys @ ..] => Ok(Expr::Map(
+            keys.iter().fold(map.clone(), |map, key| map.remove(key)),
+        )),
+        [other, ..] => Err(type_error("dissoc", "a hash map", other)),
+        [] => Err("dissoc requires at least 1 argument".to_string()),
+    }
+}
+
+// (conj coll item ...): a pvector with the items appended, a set with them
+// added, or a map with (key value) items added
+fn conj(args: &[Expr]) -> Result<Expr, String> {
+    match args {
+        [Expr::PVector(v), items @ ..] => Ok(Expr::PVector(
+            items.iter().fold(v.clone(), |v, item| v.push(item.clone())),
+        )),
+        [Expr::Set(set), items @ ..] => Ok(Expr::Set(
+            items
+                .iter()
+                .fold(set.clone(), |set, item| set.insert(item.clone())),
+        )),
+        [Expr::Map(map), items @ ..] => {
+            let mut map = map.clone();
+            for item in items {
+                match item {
+                    Expr::List(pair) if pair.len() == 2 => {
+                        map = map.insert(pair[0].clone(), pair[1].clone());
+                    }
+                    other => {
+                        return Err(format!(
+                            "conj: hash map entries must be (key value) lists, got {}",
+                            other.written()
+                        ));
+                    }
+                }
+            }
+            Ok(Expr::Map(map))
+        }
+        [other, ..] => Err(type_error("conj", "a pvector, hash set or hash map", other)),
+        [] => Err("conj requires at least 1 argument".to_string()),
+    }
+}
+
+// (disj set item ...): a set without the items
+fn disj(args: &[Expr]) -> Result<Expr, String> {
+    match args {
+        [Expr::Set(set), items @ ..] => Ok(Expr::Set(
+            items.iter().fold(set.clone(), |set, item| set.remove(item)),
+        )),
+        [other, ..] => Err(type_error("disj", "a hash set", other)),
+        [] => Err("disj requires at least 1 argument".to_string()),
+    }
+}
+
+// (pop v): a pvector without its last item
+fn pop(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("pop", args, 1)?;
+    match &args[0] {
+        Expr::PVector(v) => v
+            .pop()
+            .map(Expr::PVector)
+            .ok_or_else(|| "pop: pvector is empty".to_string()),
+        other => Err(type_error("pop", "a pvector", other)),
+    }
+}
+
+// (contains? coll key): whether a map has the key, a set has the member, or
+// a pvector has the index
+fn contains(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("contains?", args, 2)?;
+    let found = match &args[0] {
+        Expr::Map(map) => map.contains_key(&args[1]),
+        Expr::Set(set) => set.contains(&args[1]),
+        Expr::PVector(v) => matches!(args[1], Expr::Number(n) if n >= 0 && (n as usize) < v.len()),
+        other => {
+            return Err(type_error(
+                "contains?",
+                "a hash map, hash set or pvector",
+                other,
+            ));
+        }
+    };
+    Ok(Expr::Bool(found))
+}
+
+fn count(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("count", args, 1)?;
+    let len = match &args[0] {
+        Expr::Map(map) => map.len(),
+        Expr::Set(set) => set.len(),
+        Expr::PVector(v) => v.len(),
+        other => {
+            return Err(type_error(
+                "count",
+                "a hash map, hash set or pvector",
+                other,
+            ));
+        }
+    };
+    Ok(Expr::Number(len as i64))
+}
+
+// (alist->hash-map '((k1 v1) (k2 v2))). When a key appears more than once,
+// the first entry wins, as with alist->hash-table and as assoc would find it.
+fn alist_to_hash_map(args: &[Expr]) -> Result<Expr, String> {
+    let alist = list_arg_only("alist->hash-map", args)?;
+    let mut map = PersistentMap::new();
+    for entry in alist {
+        match entry {
+            Expr::List(pair) if pair.len() == 2 => {
+                if !map.contains_key(&pair[0]) {
+                    map = map.insert(pair[0].clone(), pair[1].clone());
+                }
+            }
+            other => {
+                return Err(format!(
+                    "alist->hash-map entries must be (key value) lists, got {}",
+                    other.written()
+                ));
+            }
+        }
+    }
+    Ok(Expr::Map(map))
+}
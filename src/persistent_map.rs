@@ -0,0 +1,417 @@
+use crate::equality::{hash_equal, is_equal};
+use crate::types::Expr;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::rc::Rc;
+
+// Bits of the key hash consumed per trie level
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+// An immutable map keyed by equal?, stored as a hash array mapped trie.
+// Each level of the trie indexes 5 bits of the key's hash; a branch only
+// stores the children that exist, with a bitmap recording which ones. Insert
+// and remove copy the nodes along one path and share everything else with
+// the original map, so both versions stay valid and cheap to keep around.
+// Iteration order follows the key hashes, not insertion order.
+#[derive(Debug, Clone)]
+pub struct PersistentMap {
+    root: Rc<Node>,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Branch { bitmap: u32, entries: Vec<Entry> },
+    // Keys whose full hashes are equal; every entry is a leaf
+    Collision { hash: u64, entries: Vec<Entry> },
+}
+
+#[derive(Debug, Clone)]
+enum Entry {
+    Leaf(Rc<Leaf>),
+    Node(Rc<Node>),
+}
+
+#[derive(Debug)]
+struct Leaf {
+    hash: u64,
+    key: Expr,
+    value: Expr,
+}
+
+fn leaf(hash: u64, key: Expr, value: Expr) -> Entry {
+    Entry::Leaf(Rc::new(Leaf { hash, key, value }))
+}
+
+impl Node {
+    fn empty() -> Node {
+        Node::Branch {
+            bitmap: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    fn entries(&self) -> &[Entry] {
+        match self {
+            Node::Branch { entries, .. } | Node::Collision { entries, .. } => entries,
+        }
+    }
+}
+
+fn hash_key(key: &Expr) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_equal(key, &mut hasher);
+    hasher.finish()
+}
+
+// The bit for `hash` in a branch at `shift`, and the position of that
+// child among the entries present in `bitmap`
+fn slot(bitmap: u32, hash: u64, shift: u32) -> (u32, usize) {
+    let bit = 1 << ((hash >> shift) & MASK);
+    (bit, (bitmap & (bit - 1)).count_ones() as usize)
+}
+
+impl PersistentMap {
+    pub fn new() -> Self {
+        PersistentMap {
+            root: Rc::new(Node::empty()),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Whether both maps are the same version (used by eqv?)
+    pub fn ptr_eq(&self, other: &PersistentMap) -> bool {
+        Rc::ptr_eq(&self.root, &other.root)
+    }
+
+    // Address of the root, shared by maps that are ptr_eq (used by hash_eqv)
+    pub fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(&self.root).cast()
+    }
+
+    pub fn get(&self, key: &Expr) -> Option<&Expr> {
+        self.get_entry(key).map(|(_, value)| value)
+    }
+
+    // The stored key equal? to `key`, with its value
+    pub fn get_entry(&self, key: &Expr) -> Option<(&Expr, &Expr)> {
+        let hash = hash_key(key);
+        let mut node = &*self.root;
+        let mut shift = 0;
+        loop {
+            let entry = match node {
+                Node::Branch { bitmap, entries } => {
+                    let (bit, index) = slot(*bitmap, hash, shift);
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+                    &entries[index]
+                }
+                Node::Collision { entries, .. } => {
+                    return entries.iter().find_map(|e| match e {
+                        Entry::Leaf(leaf) if is_equal(&leaf.key, key) => {
+                            Some((&leaf.key, &leaf.value))
+                        }
+                        _ => None,
+                    });
+                }
+            };
+            match entry {
+                Entry::Leaf(leaf) => {
+                    return if is_equal(&leaf.key, key) {
+                        Some((&leaf.key, &leaf.value))
+                    } else {
+                        None
+                    };
+                }
+                Entry::Node(child) => {
+                    node = child;
+                    shift += BITS;
+                }
+            }
+        }
+    }
+
+    pub fn contains_key(&self, key: &Expr) -> bool {
+        self.get(key).is_some()
+    }
+
+    // A new map with `key` bound to `value`
+    pub fn insert(&self, key: Expr, value: Expr) -> PersistentMap {
+        let hash = hash_key(&key);
+        let (root, added) = insert(&self.root, 0, hash, key, value);
+        PersistentMap {
+            root: Rc::new(root),
+            len: self.len + added as usize,
+        }
+    }
+
+    // A new map without `key`; returns a copy sharing everything when the
+    // key is not present
+    pub fn remove(&self, key: &Expr) -> PersistentMap {
+        match remove(&self.root, 0, hash_key(key), key) {
+            Some(root) => PersistentMap {
+                root: Rc::new(root),
+                len: self.len - 1,
+            },
+            None => self.clone(),
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            stack: vec![self.root.entries().iter()],
+        }
+    }
+}
+
+impl Default for PersistentMap {
+    fn default() -> Self {
+        PersistentMap::new()
+    }
+}
+
+// Two maps are equal when they bind equal? keys to equal values,
+// whatever the shape of their tries
+impl PartialEq for PersistentMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter()
+                .all(|(k, v)| other.get(k).is_some_and(|w| w == v))
+    }
+}
+
+// Returns the new node and whether a new key was added (rather than an
+// existing key rebound)
+fn insert(node: &Node, shift: u32, hash: u64, key: Expr, value: Expr) -> (Node, bool) {
+    match node {
+        Node::Branch { bitmap, entries } => {
+            let (bit, index) = slot(*bitmap, hash, shift);
+            let mut entries = entries.clone();
+            if bitmap & bit == 0 {
+                entries.insert(index, leaf(hash, key, value));
+                let node = Node::Branch {
+                    bitmap: bitmap | bit,
+                    entries,
+                };
+                return (node, true);
+            }
+            let added = match &entries[index] {
+                Entry::Leaf(existing) if is_equal(&existing.key, &key) => {
+                    entries[index] = leaf(hash, key, value);
+                    false
+                }
+                Entry::Leaf(existing) => {
+                    let merged = merge(
+                        existing.clone(),
+                        Rc::new(Leaf { hash, key, value }),
+                        shift + BITS,
+                    );
+                    entries[index] = Entry::Node(Rc::new(merged));
+                    true
+                }
+                Entry::Node(child) => {
+                    let (child, added) = insert(child, shift + BITS, hash, key, value);
+                    entries[index] = Entry::Node(Rc::new(child));
+                    added
+                }
+            };
+            let node = Node::Branch {
+                bitmap: *bitmap,
+                entries,
+            };
+            (node, added)
+        }
+        Node::Collision {
+            hash: shared,
+            entries,
+        } if *shared == hash => {
+            let mut entries = entries.clone();
+            let existing = entries
+                .iter()
+                .position(|e| matches!(e, Entry::Leaf(l) if is_equal(&l.key, &key)));
+            let new_leaf = leaf(hash, key, value);
+            let added = match existing {
+                Some(i) => {
+                    entries[i] = new_leaf;
+                    false
+                }
+                None => {
+                    entries.push(new_leaf);
+                    true
+                }
+            };
+            let node = Node::Collision {
+                hash: *shared,
+                entries,
+            };
+            (node, added)
+        }
+        Node::Collision { hash: shared, .. } => {
+            // A different hash that shares this node's prefix: push the
+            // collision node one level down under a branch
+            let (bit, _) = slot(0, *shared, shift);
+            let branch = Node::Branch {
+                bitmap: bit,
+                entries: vec![Entry::Node(Rc::new(node.clone()))],
+            };
+            insert(&branch, shift, hash, key, value)
+        }
+    }
+}
+
+// A node holding two leaves whose hashes agree below `shift`
+fn merge(a: Rc<Leaf>, b: Rc<Leaf>, shift: u32) -> Node {
+    if a.hash == b.hash {
+        return Node::Collision {
+            hash: a.hash,
+            entries: vec![Entry::Leaf(a), Entry::Leaf(b)],
+        };
+    }
+    let (bit_a, _) = slot(0, a.hash, shift);
+    let (bit_b, _) = slot(0, b.hash, shift);
+    if bit_a == bit_b {
+        return Node::Branch {
+            bitmap: bit_a,
+            entries: vec![Entry::Node(Rc::new(merge(a, b, shift + BITS)))],
+        };
+    }
+    let entries = if bit_a < bit_b {
+        vec![Entry::Leaf(a), Entry::Leaf(b)]
+    } else {
+        vec![Entry::Leaf(b), Entry::Leaf(a)]
+    };
+    Node::Branch {
+        bitmap: bit_a | bit_b,
+        entries,
+    }
+}
+
+// Returns None when the key is not present. A child left with a single leaf
+// is replaced by that leaf, so removing keys undoes the nesting inserts made.
+fn remove(node: &Node, shift: u32, hash: u64, key: &Expr) -> Option<Node> {
+    match node {
+        Node::Branch { bitmap, entries } => {
+            let (bit, index) = slot(*bitmap, hash, shift);
+            if bitmap & bit == 0 {
+                return None;
+            }
+            let mut entries = entries.clone();
+            let mut bitmap = *bitmap;
+            match &entries[index] {
+                Entry::Leaf(leaf) if is_equal(&leaf.key, key) => {
+                    entries.remove(index);
+                    bitmap &= !bit;
+                }
+                Entry::Leaf(_) => return None,
+                Entry::Node(child) => {
+                    let child = remove(child, shift + BITS, hash, key)?;
+                    entries[index] = match child.entries() {
+                        [leaf @ Entry::Leaf(_)] => leaf.clone(),
+                        _ => Entry::Node(Rc::new(child)),
+                    };
+                }
+            }
+            Some(Node::Branch { bitmap, entries })
+        }
+        Node::Collision {
+            hash: shared,
+            entries,
+        } => {
+            let index = entries
+                .iter()
+                .position(|e| matches!(e, Entry::Leaf(leaf) if is_equal(&leaf.key, key)))?;
+            let mut entries = entries.clone();
+            entries.remove(index);
+            Some(Node::Collision {
+                hash: *shared,
+                entries,
+            })
+        }
+    }
+}
+
+// Depth-first iteration over the trie's leaves
+pub struct Iter<'a> {
+    stack: Vec<std::slice::Iter<'a, Entry>>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a Expr, &'a Expr);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.last_mut()?.next() {
+                None => {
+                    self.stack.pop();
+                }
+                Some(Entry::Leaf(leaf)) => return Some((&leaf.key, &leaf.value)),
+                Some(Entry::Node(child)) => self.stack.push(child.entries().iter()),
+            }
+        }
+    }
+}
+
+// An immutable set of values compared with equal?, sharing structure the
+// same way as PersistentMap (it is a map whose values are ignored)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PersistentSet {
+    map: PersistentMap,
+}
+
+impl PersistentSet {
+    pub fn new() -> Self {
+        PersistentSet::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn ptr_eq(&self, other: &PersistentSet) -> bool {
+        self.map.ptr_eq(&other.map)
+    }
+
+    pub fn as_ptr(&self) -> *const () {
+        self.map.as_ptr()
+    }
+
+    pub fn contains(&self, item: &Expr) -> bool {
+        self.map.contains_key(item)
+    }
+
+    // The member equal? to `item`, if any
+    pub fn get(&self, item: &Expr) -> Option<&Expr> {
+        self.map.get_entry(item).map(|(k, _)| k)
+    }
+
+    pub fn insert(&self, item: Expr) -> PersistentSet {
+        PersistentSet {
+            map: self.map.insert(item, Expr::Bool(true)),
+        }
+    }
+
+    pub fn remove(&self, item: &Expr) -> PersistentSet {
+        PersistentSet {
+            map: self.map.remove(item),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Expr> {
+        self.map.iter().map(|(k, _)| k)
+    }
+}
@@ -0,0 +1,269 @@
+use crate::types::Expr;
+use std::rc::Rc;
+
+// Bits of the index consumed per trie level, so each node holds 32 children
+const BITS: u32 = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+// An immutable vector stored as a 32-way trie of leaves holding 32 items each.
+// The last (up to 32) items live in a separate tail, so pushing usually just
+// copies the tail, and a full tail is moved into the trie in one step.
+// Setting an index copies the nodes on the path to it and shares the rest
+// with the original vector.
+#[derive(Debug, Clone)]
+pub struct PersistentVector {
+    len: usize,
+    // Bit offset of the root's children: 5 for a root whose children are
+    // leaves, growing by 5 each time the trie gains a level
+    shift: u32,
+    root: Rc<Node>,
+    tail: Rc<Vec<Expr>>,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Branch(Vec<Rc<Node>>),
+    Leaf(Vec<Expr>),
+}
+
+impl Node {
+    fn children(&self) -> &[Rc<Node>] {
+        match self {
+            Node::Branch(children) => children,
+            Node::Leaf(_) => unreachable!("leaves only appear at the bottom level"),
+        }
+    }
+}
+
+// A chain of single-child branches from `level` down to `leaf`
+fn new_path(level: u32, leaf: Rc<Node>) -> Rc<Node> {
+    if level == 0 {
+        leaf
+    } else {
+        Rc::new(Node::Branch(vec![new_path(level - BITS, leaf)]))
+    }
+}
+
+impl PersistentVector {
+    pub fn new() -> Self {
+        PersistentVector {
+            len: 0,
+            shift: BITS,
+            root: Rc::new(Node::Branch(Vec::new())),
+            tail: Rc::new(Vec::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Whether both vectors are the same version (used by eqv?)
+    pub fn ptr_eq(&self, other: &PersistentVector) -> bool {
+        self.len == other.len
+            && Rc::ptr_eq(&self.root, &other.root)
+            && Rc::ptr_eq(&self.tail, &other.tail)
+    }
+
+    // Address of the tail, shared by vectors that are ptr_eq (used by hash_eqv)
+    pub fn as_ptr(&self) -> *const () {
+        Rc::as_ptr(&self.tail).cast()
+    }
+
+    // Index of the first item stored in the tail
+    fn tail_offset(&self) -> usize {
+        if self.len < WIDTH {
+            0
+        } else {
+            ((self.len - 1) >> BITS) << BITS
+        }
+    }
+
+    // The 32 items containing index `i`
+    fn leaf_for(&self, i: usize) -> &[Expr] {
+        if i >= self.tail_offset() {
+            return &self.tail;
+        }
+        let mut node = &*self.root;
+        let mut level = self.shift;
+        while level > 0 {
+            node = &node.children()[(i >> level) & MASK];
+            level -= BITS;
+        }
+        match node {
+            Node::Leaf(items) => items,
+            Node::Branch(_) => unreachable!("the bottom level holds leaves"),
+        }
+    }
+
+    pub fn get(&self, i: usize) -> Option<&Expr> {
+        if i >= self.len {
+            return None;
+        }
+        Some(&self.leaf_for(i)[i & MASK])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Expr> {
+        (0..self.len).map(|i| &self.leaf_for(i)[i & MASK])
+    }
+
+    // A new vector with `item` appended
+    pub fn push(&self, item: Expr) -> PersistentVector {
+        if self.len - self.tail_offset() < WIDTH {
+            let mut tail = (*self.tail).clone();
+            tail.push(item);
+            return PersistentVector {
+                len: self.len + 1,
+                shift: self.shift,
+                root: self.root.clone(),
+                tail: Rc::new(tail),
+            };
+        }
+        // The tail is full: move it into the trie and start a new one
+        let leaf = Rc::new(Node::Leaf((*self.tail).clone()));
+        let (root, shift) = if (self.len >> BITS) > (1 << self.shift) {
+            // The trie is full at this height, so it gains a level
+            let root = Node::Branch(vec![self.root.clone(), new_path(self.shift, leaf)]);
+            (Rc::new(root), self.shift + BITS)
+        } else {
+            (self.push_leaf(self.shift, &self.root, leaf), self.shift)
+        };
+        PersistentVector {
+            len: self.len + 1,
+            shift,
+            root,
+            tail: Rc::new(vec![item]),
+        }
+    }
+
+    fn push_leaf(&self, level: u32, parent: &Node, leaf: Rc<Node>) -> Rc<Node> {
+        let index = ((self.len - 1) >> level) & MASK;
+        let mut children = parent.children().to_vec();
+        let child = if level == BITS {
+            leaf
+        } else if let Some(child) = children.get(index) {
+            self.push_leaf(level - BITS, child, leaf)
+        } else {
+            new_path(level - BITS, leaf)
+        };
+        if index < children.len() {
+            children[index] = child;
+        } else {
+            children.push(child);
+        }
+        Rc::new(Node::Branch(children))
+    }
+
+    // A new vector with index `i` replaced by `item`, or None when `i` is
+    // out of range
+    pub fn set(&self, i: usize, item: Expr) -> Option<PersistentVector> {
+        if i >= self.len {
+            return None;
+        }
+        let mut result = self.clone();
+        if i >= self.tail_offset() {
+            let mut tail = (*self.tail).clone();
+            tail[i & MASK] = item;
+            result.tail = Rc::new(tail);
+        } else {
+            result.root = set_in(self.shift, &self.root, i, item);
+        }
+        Some(result)
+    }
+
+    // A new vector without its last item, or None when it is empty
+    pub fn pop(&self) -> Option<PersistentVector> {
+        match self.len {
+            0 => return None,
+            1 => return Some(PersistentVector::new()),
+            _ => {}
+        }
+        if self.len - self.tail_offset() > 1 {
+            let mut tail = (*self.tail).clone();
+            tail.pop();
+            return Some(PersistentVector {
+                len: self.len - 1,
+                tail: Rc::new(tail),
+                ..self.clone()
+            });
+        }
+        // The tail empties: the last leaf of the trie becomes the new tail
+        let tail = self.leaf_for(self.len - 2).to_vec();
+        let mut root = self
+            .pop_leaf(self.shift, &self.root)
+            .unwrap_or_else(|| Rc::new(Node::Branch(Vec::new())));
+        let mut shift = self.shift;
+        if shift > BITS && root.children().len() == 1 {
+            root = root.children()[0].clone();
+            shift -= BITS;
+        }
+        Some(PersistentVector {
+            len: self.len - 1,
+            shift,
+            root,
+            tail: Rc::new(tail),
+        })
+    }
+
+    // Removes the trie's last leaf; returns None when `node` becomes empty
+    fn pop_leaf(&self, level: u32, node: &Node) -> Option<Rc<Node>> {
+        let index = ((self.len - 2) >> level) & MASK;
+        let mut children = node.children()[..=index].to_vec();
+        if level > BITS {
+            match self.pop_leaf(level - BITS, &children[index]) {
+                Some(child) => children[index] = child,
+                None => {
+                    children.pop();
+                }
+            }
+        } else {
+            children.pop();
+        }
+        if children.is_empty() {
+            None
+        } else {
+            Some(Rc::new(Node::Branch(children)))
+        }
+    }
+}
+
+fn set_in(level: u32, node: &Node, i: usize, item: Expr) -> Rc<Node> {
+    match node {
+        Node::Leaf(items) => {
+            let mut items = items.clone();
+            items[i & MASK] = item;
+            Rc::new(Node::Leaf(items))
+        }
+        Node::Branch(children) => {
+            let index = (i >> level) & MASK;
+            let mut children = children.clone();
+            children[index] = set_in(level - BITS, &children[index], i, item);
+            Rc::new(Node::Branch(children))
+        }
+    }
+}
+
+impl Default for PersistentVector {
+    fn default() -> Self {
+        PersistentVector::new()
+    }
+}
+
+impl FromIterator<Expr> for PersistentVector {
+    fn from_iter<I: IntoIterator<Item = Expr>>(items: I) -> Self {
+        items
+            .into_iter()
+            .fold(PersistentVector::new(), |v, item| v.push(item))
+    }
+}
+
+impl PartialEq for PersistentVector {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+}
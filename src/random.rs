@@ -0,0 +1,161 @@
+use crate::env::Env;
+use crate::strings::expect_args;
+use crate::types::Expr;
+use crate::vectors::new_vector;
+
+pub const BUILTINS: &[&str] = &[
+    "random-integer",
+    "random-real",
+    "random-seed!",
+    "shuffle",
+    "random-choice",
+];
+
+// The seed a new interpreter starts from, so runs are reproducible unless a
+// script or the command line picks another one
+pub const DEFAULT_SEED: u64 = 0;
+
+// A xoshiro256** pseudo-random generator. Not suitable for cryptography, but
+// fast, well distributed and fully determined by its seed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // Spread the seed over the 256-bit state with SplitMix64, which never
+        // produces the all-zero state xoshiro cannot leave
+        let mut x = seed;
+        let mut next = || {
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+        Rng {
+            state: [next(), next(), next(), next()],
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    // A uniformly distributed integer in 0..n; n must be positive
+    pub fn below(&mut self, n: u64) -> u64 {
+        // Reject the values past the last whole multiple of n, which would
+        // otherwise make small results slightly more likely
+        let zone = u64::MAX - (u64::MAX % n);
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+
+    // A uniformly distributed real strictly between 0 and 1
+    pub fn next_f64(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Rng::new(DEFAULT_SEED)
+    }
+}
+
+// Built-in random number procedures. Returns None when `name` is not one of
+// them. They all draw from the environment's generator, which closures share.
+pub fn call(name: &str, args: &[Expr], env: &mut Env) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "random-integer" => random_integer(args, env),
+        "random-real" => expect_args(name, args, 0).map(|_| Expr::Real(env.rng().next_f64())),
+        "random-seed!" => random_seed(args, env),
+        "shuffle" => shuffle(args, env),
+        "random-choice" => random_choice(args, env),
+        _ => return None,
+    };
+    Some(result)
+}
+
+// (random-integer n): an integer from 0 to n - 1
+fn random_integer(args: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    expect_args("random-integer", args, 1)?;
+    match &args[0] {
+        Expr::Number(n) if *n > 0 => Ok(Expr::Number(env.rng().below(*n as u64) as i64)),
+        other => Err(format!(
+            "random-integer requires a positive integer, got {}",
+            other.written()
+        )),
+    }
+}
+
+fn random_seed(args: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    expect_args("random-seed!", args, 1)?;
+    match &args[0] {
+        Expr::Number(seed) => {
+            env.set_random_seed(*seed as u64);
+            Ok(Expr::Unspecified)
+        }
+        other => Err(format!(
+            "random-seed! requires an integer seed, got {}",
+            other.written()
+        )),
+    }
+}
+
+// The items of a list, vector or pvector
+fn sequence_items(name: &str, expr: &Expr) -> Result<Vec<Expr>, String> {
+    match expr {
+        Expr::List(items) => Ok(items.clone()),
+        Expr::Vector(items) => Ok(items.borrow().clone()),
+        Expr::PVector(items) => Ok(items.iter().cloned().collect()),
+        other => Err(format!(
+            "{} requires a list or vector, got {}",
+            name,
+            other.written()
+        )),
+    }
+}
+
+// (shuffle seq): a new sequence of the same kind with the items in random
+// order; the argument is left unchanged
+fn shuffle(args: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    expect_args("shuffle", args, 1)?;
+    let mut items = sequence_items("shuffle", &args[0])?;
+    // Fisher-Yates: every permutation is equally likely
+    let mut rng = env.rng();
+    for i in (1..items.len()).rev() {
+        let j = rng.below(i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+    Ok(match &args[0] {
+        Expr::Vector(_) => new_vector(items),
+        Expr::PVector(_) => Expr::PVector(items.into_iter().collect()),
+        _ => Expr::List(items),
+    })
+}
+
+// (random-choice seq): one item of a non-empty list or vector
+fn random_choice(args: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    expect_args("random-choice", args, 1)?;
+    let mut items = sequence_items("random-choice", &args[0])?;
+    if items.is_empty() {
+        return Err("random-choice requires a non-empty sequence".to_string());
+    }
+    let i = env.rng().below(items.len() as u64) as usize;
+    Ok(items.swap_remove(i))
+}
@@ -0,0 +1,225 @@
+use crate::bytevectors::byte_arg;
+use crate::env::Env;
+use crate::eval::apply;
+use crate::strings::expect_args;
+use crate::types::Expr;
+use crate::vectors::new_vector;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+pub const BUILTINS: &[&str] = &["set-reader-macro!", "remove-reader-macro!", "reader-macro?"];
+
+// Turns the datum read after a reader macro into the expression read in its
+// place
+pub type ReaderMacro = Rc<dyn Fn(Expr) -> Result<Expr, String>>;
+
+// The reader's prefix syntax: each entry maps the text of a macro to the
+// handler that expands it. A macro is one of
+// - a prefix character, such as ', read as a token of its own wherever a
+//   token starts; the datum after it is passed to the handler. It is an
+//   ASCII punctuation character that cannot start a number or a symbol:
+//   one of ' ` , @ [ ] { } | \
+// - a dispatch prefix, #name, such as #date; the datum after it (with or
+//   without a space between) is passed to the handler
+// - #( or #u8(, whose handlers get the items up to the ) as a list
+// The default table holds ' (quote), #( (vectors) and #u8( (bytevectors).
+#[derive(Clone)]
+pub struct Readtable {
+    macros: HashMap<String, ReaderMacro>,
+    // The single-character macros, for the lexer
+    prefix_chars: Vec<char>,
+}
+
+impl Readtable {
+    pub fn new() -> Readtable {
+        let mut table = Readtable {
+            macros: HashMap::new(),
+            prefix_chars: Vec::new(),
+        };
+        table.insert("'", |datum| {
+            Ok(Expr::List(vec![Expr::Symbol("quote".into()), datum]))
+        });
+        table.insert("#(", |items| Ok(new_vector(list_items(items))));
+        table.insert("#u8(", |items| {
+            let bytes = list_items(items)
+                .iter()
+                .map(|item| byte_arg("bytevector literal", item))
+                .collect::<Result<Vec<u8>, String>>()?;
+            Ok(Expr::Bytevector(Rc::new(RefCell::new(bytes))))
+        });
+        table.prefix_chars.push('\'');
+        table
+    }
+
+    // Adds or replaces the macro `name`, which must be a single prefix
+    // character, #name, #( or #u8(
+    pub fn set(
+        &mut self,
+        name: &str,
+        handler: impl Fn(Expr) -> Result<Expr, String> + 'static,
+    ) -> Result<(), String> {
+        check_name(name)?;
+        if let Some(c) = prefix_char(name)
+            && !self.prefix_chars.contains(&c)
+        {
+            self.prefix_chars.push(c);
+        }
+        self.insert(name, handler);
+        Ok(())
+    }
+
+    fn insert(&mut self, name: &str, handler: impl Fn(Expr) -> Result<Expr, String> + 'static) {
+        self.macros.insert(name.to_string(), Rc::new(handler));
+    }
+
+    // Removes the macro `name`, returning whether there was one
+    pub fn remove(&mut self, name: &str) -> bool {
+        if let Some(c) = prefix_char(name) {
+            self.prefix_chars.retain(|&p| p != c);
+        }
+        self.macros.remove(name).is_some()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.macros.contains_key(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ReaderMacro> {
+        self.macros.get(name)
+    }
+
+    pub fn prefix_chars(&self) -> &[char] {
+        &self.prefix_chars
+    }
+
+    // Runs the macro `name` on `datum`, or returns None if there is no such
+    // macro
+    pub(crate) fn expand(&self, name: &str, datum: Expr) -> Option<Result<Expr, String>> {
+        self.macros.get(name).map(|handler| handler(datum))
+    }
+}
+
+impl Default for Readtable {
+    fn default() -> Self {
+        Readtable::new()
+    }
+}
+
+impl fmt::Debug for Readtable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&String> = self.macros.keys().collect();
+        names.sort();
+        f.debug_struct("Readtable").field("macros", &names).finish()
+    }
+}
+
+// Two tables are equal when they hold the very same handlers
+impl PartialEq for Readtable {
+    fn eq(&self, other: &Self) -> bool {
+        self.macros.len() == other.macros.len()
+            && self.macros.iter().all(|(name, handler)| {
+                other
+                    .macros
+                    .get(name)
+                    .is_some_and(|h| Rc::ptr_eq(handler, h))
+            })
+    }
+}
+
+fn list_items(datum: Expr) -> Vec<Expr> {
+    match datum {
+        Expr::List(items) => items,
+        other => vec![other],
+    }
+}
+
+fn prefix_char(name: &str) -> Option<char> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+fn check_name(name: &str) -> Result<(), String> {
+    let delimiter = |c: char| c.is_whitespace() || matches!(c, '(' | ')' | '"' | ';');
+    let valid = match prefix_char(name) {
+        // Not a character that can start a number or a symbol, such as + or
+        // !, or symbols like +inf.0 and set! would no longer read
+        Some(c) => {
+            c.is_ascii_punctuation()
+                && !delimiter(c)
+                && c != '#'
+                && !"+-.!$%&*/:<=>?^_~".contains(c)
+        }
+        None => match name.strip_prefix('#') {
+            Some(rest) => {
+                name == "#(" || name == "#u8(" || {
+                    !rest.is_empty()
+                        && !rest.chars().any(delimiter)
+                        && !matches!(rest.chars().next(), Some('|' | ';' | '\\' | ':'))
+                }
+            }
+            None => false,
+        },
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid reader macro name {:?}: expected a prefix character, #name, #( or #u8(",
+            name
+        ))
+    }
+}
+
+// Built-in readtable procedures. Returns None when `name` is not one of them.
+// They change the environment's readtable, which affects forms read after the
+// one that calls them.
+pub fn call(name: &str, args: &[Expr], env: &mut Env) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "set-reader-macro!" => set_reader_macro(args, env),
+        "remove-reader-macro!" => name_arg(name, args, 1)
+            .map(|macro_name| Expr::Bool(env.update_readtable(|table| table.remove(macro_name)))),
+        "reader-macro?" => name_arg(name, args, 1)
+            .map(|macro_name| Expr::Bool(env.readtable().contains(macro_name))),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn name_arg<'a>(name: &str, args: &'a [Expr], count: usize) -> Result<&'a str, String> {
+    expect_args(name, args, count)?;
+    match &args[0] {
+        Expr::Str(s) => Ok(s),
+        other => Err(format!(
+            "{} requires a string naming the macro, got {}",
+            name,
+            other.written()
+        )),
+    }
+}
+
+// (set-reader-macro! "#name" procedure): from now on #name datum reads as
+// whatever (procedure 'datum) returns
+fn set_reader_macro(args: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    let macro_name = name_arg("set-reader-macro!", args, 2)?;
+    let procedure = match &args[1] {
+        proc @ (Expr::Lambda { .. } | Expr::Builtin(_)) => proc.clone(),
+        other => {
+            return Err(format!(
+                "set-reader-macro! requires a procedure, got {}",
+                other.written()
+            ));
+        }
+    };
+    let closure_env = env.closure_copy();
+    env.update_readtable(|table| {
+        table.set(macro_name, move |datum| {
+            apply(&procedure, vec![datum], &mut closure_env.clone())
+        })
+    })?;
+    Ok(Expr::Unspecified)
+}
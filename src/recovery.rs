@@ -0,0 +1,312 @@
+use crate::parser::{DEFAULT_MAX_DEPTH, ParseError, Program, parse_atom};
+use crate::readtable::Readtable;
+use crate::source_map::SourceMap;
+use crate::tokenizer::{
+    Lexer, Span, Token, TokenKind, lex_error, read_char_literal, read_string_literal,
+};
+use crate::types::Expr;
+
+// Reads as much of a program as it can, for editor tooling. A problem does
+// not stop the parse: it is recorded as a diagnostic, and the part of the tree
+// it spoils becomes an `Expr::Error` node. A missing ) closes the list at the
+// end of the input, or before a ( in the first column (which most likely
+// starts the next top-level form); a stray ) is skipped; an unterminated
+// string ends at the end of its line.
+pub fn parse_recovering(source: &str) -> (Program, Vec<ParseError>) {
+    parse_recovering_with(source, &Readtable::default())
+}
+
+// Reads as much of a program as it can with the reader macros in `readtable`
+pub fn parse_recovering_with(source: &str, readtable: &Readtable) -> (Program, Vec<ParseError>) {
+    let mut reader = Recovery {
+        source,
+        lexer: Lexer::new(source).with_readtable(readtable),
+        peeked: None,
+        last_end: Span {
+            line: 1,
+            column: 1,
+            ..Span::default()
+        },
+        source_map: SourceMap::new(),
+        diagnostics: Vec::new(),
+        readtable,
+    };
+    let mut program = Program::default();
+    while let Some(token) = reader.peek() {
+        if token.kind == TokenKind::RParen {
+            reader.advance();
+            reader.report("unexpected ')' with no list to close", token.span);
+            continue;
+        }
+        let (expr, span) = reader.form();
+        program.forms.push(expr);
+        program.spans.push(span);
+    }
+    program.source_map = reader.source_map;
+    // Report problems in the order they appear in the source
+    let mut diagnostics = reader.diagnostics;
+    diagnostics.sort_by_key(|d| d.span.start);
+    (program, diagnostics)
+}
+
+struct Recovery<'a, 'r> {
+    source: &'a str,
+    lexer: Lexer<'a, 'r>,
+    peeked: Option<Token<'a>>,
+    // The position just past the last token read
+    last_end: Span,
+    source_map: SourceMap,
+    diagnostics: Vec<ParseError>,
+    readtable: &'r Readtable,
+}
+
+// A list, or a quote or other reader macro, still waiting for the rest of its
+// input
+enum Open<'a> {
+    List { start: Token<'a>, items: Vec<Expr> },
+    Quote { start: Token<'a> },
+}
+
+impl<'a> Recovery<'a, '_> {
+    fn peek(&mut self) -> Option<Token<'a>> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next();
+        }
+        self.peeked
+    }
+
+    fn advance(&mut self) -> Option<Token<'a>> {
+        let token = self.peek()?;
+        self.peeked = None;
+        self.last_end = token.end_position();
+        Some(token)
+    }
+
+    fn report(&mut self, message: &str, span: Span) {
+        self.diagnostics.push(ParseError {
+            message: message.to_string(),
+            span,
+        });
+    }
+
+    // Records the problem and returns the error node standing in for it
+    fn error_node(&mut self, message: String, span: Span) -> (Expr, Span) {
+        let node = Expr::Error(message.as_str().into());
+        self.diagnostics.push(ParseError { message, span });
+        (node, span)
+    }
+
+    // Reads one form; there must be a token left. Open lists and quotes are
+    // kept on a stack of their own rather than by recursion, so deep input
+    // can't overflow the call stack.
+    fn form(&mut self) -> (Expr, Span) {
+        let mut open: Vec<Open> = Vec::new();
+        loop {
+            let value = match open.last() {
+                Some(Open::List { start, .. }) => {
+                    let start = *start;
+                    match self.list_end(start) {
+                        Some(end) => {
+                            let Some(Open::List { items, .. }) = open.pop() else {
+                                unreachable!("the list was just looked at");
+                            };
+                            Some(self.close_list(start, items, end))
+                        }
+                        None => self.next_value(&mut open),
+                    }
+                }
+                _ => self.next_value(&mut open),
+            };
+            let Some(mut finished) = value else {
+                continue;
+            };
+
+            // Hand the finished value to whatever is waiting for it
+            loop {
+                match open.pop() {
+                    None => return finished,
+                    Some(Open::List { start, mut items }) => {
+                        items.push(finished.0);
+                        open.push(Open::List { start, items });
+                        break;
+                    }
+                    Some(Open::Quote { start }) => {
+                        let span = Span {
+                            end: finished.1.end,
+                            ..start.span
+                        };
+                        finished = self.expand(start.text, finished.0, span);
+                    }
+                }
+            }
+        }
+    }
+
+    // Reads the next token of a form: a whole value, or the start of a list
+    // or quote, which is pushed onto `open`
+    fn next_value(&mut self, open: &mut Vec<Open<'a>>) -> Option<(Expr, Span)> {
+        let token = self.advance().expect("form read past the end of input");
+        let span = token.span;
+        // ' and dispatch macros such as #date read the datum after them
+        if self.is_macro(token)
+            && self
+                .peek()
+                .is_none_or(|next| next.kind == TokenKind::RParen)
+        {
+            let message = format!("missing datum after {}", token.text);
+            return Some(self.error_node(message, span));
+        }
+        let opens = match token.kind {
+            TokenKind::LParen | TokenKind::VectorOpen | TokenKind::BytevectorOpen => Open::List {
+                start: token,
+                items: Vec::new(),
+            },
+            TokenKind::Quote => Open::Quote { start: token },
+            TokenKind::Atom if self.is_macro(token) => Open::Quote { start: token },
+            TokenKind::String if token.is_unterminated() => {
+                return Some(self.unterminated_string(token));
+            }
+            TokenKind::String => {
+                return Some(match read_string_literal(token.text) {
+                    Ok(text) => (Expr::Str(text.into()), span),
+                    Err(message) => self.error_node(message, span),
+                });
+            }
+            TokenKind::Char => {
+                return Some(match read_char_literal(token.text) {
+                    Ok(c) => (Expr::Char(c), span),
+                    Err(message) => self.error_node(message, span),
+                });
+            }
+            TokenKind::Error => return Some(self.error_node(lex_error(token.text), span)),
+            TokenKind::Atom | TokenKind::RParen => {
+                return Some(match parse_atom(token.text) {
+                    Ok(expr) => (expr, span),
+                    Err(message) => self.error_node(message, span),
+                });
+            }
+        };
+        if open.len() >= DEFAULT_MAX_DEPTH {
+            return Some(self.too_deep(token));
+        }
+        open.push(opens);
+        None
+    }
+
+    // Where the list opened by `start` ends, if it ends before the next
+    // token: at a ), or early, if the ) is missing
+    fn list_end(&mut self, start: Token) -> Option<usize> {
+        match self.peek() {
+            Some(token) if token.kind == TokenKind::RParen => {
+                self.advance();
+                return Some(token.span.end);
+            }
+            Some(token)
+                if token.kind == TokenKind::LParen
+                    && token.span.column == 1
+                    && token.span.line > start.span.line => {}
+            Some(_) => return None,
+            None => {}
+        }
+        self.report("unclosed list: missing ')'", start.span);
+        Some(self.last_end.start)
+    }
+
+    // The list, vector or bytevector opened by `start`
+    fn close_list(&mut self, start: Token, items: Vec<Expr>, end: usize) -> (Expr, Span) {
+        let span = Span { end, ..start.span };
+        if start.kind == TokenKind::LParen {
+            self.source_map.record(&items, span);
+            (Expr::List(items), span)
+        } else {
+            self.expand(start.text, Expr::List(items), span)
+        }
+    }
+
+    // Whether `token` is a reader macro that reads the datum after it
+    fn is_macro(&self, token: Token) -> bool {
+        match token.kind {
+            TokenKind::Quote => true,
+            TokenKind::Atom => self.readtable.contains(token.text),
+            _ => false,
+        }
+    }
+
+    // Runs the reader macro `name`, standing an error node in for the value
+    // if it fails
+    fn expand(&mut self, name: &str, datum: Expr, span: Span) -> (Expr, Span) {
+        match self.readtable.expand(name, datum) {
+            Some(Ok(value)) => {
+                if let Expr::List(items) = &value {
+                    self.source_map.record(items, span);
+                }
+                (value, span)
+            }
+            Some(Err(message)) => self.error_node(message, span),
+            None => self.error_node(format!("unknown reader syntax {}", name), span),
+        }
+    }
+
+    // Skips the rest of a form nested too deeply to read safely: up to the )
+    // that closes `open`, or the end of the input. This loops rather than
+    // recursing, however deep the input goes.
+    fn too_deep(&mut self, open: Token) -> (Expr, Span) {
+        let mut depth = 0;
+        let mut current = open;
+        loop {
+            match current.kind {
+                TokenKind::LParen | TokenKind::VectorOpen | TokenKind::BytevectorOpen => depth += 1,
+                TokenKind::RParen => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 && !self.is_macro(current) {
+                break;
+            }
+            match self.peek() {
+                Some(next) if depth > 0 || next.kind != TokenKind::RParen => {
+                    self.advance();
+                    current = next;
+                }
+                _ => break,
+            }
+        }
+        let span = Span {
+            end: self.last_end.start,
+            ..open.span
+        };
+        self.error_node(
+            format!("nesting too deep: more than {} levels", DEFAULT_MAX_DEPTH),
+            span,
+        )
+    }
+
+    // A string with no closing quote runs to the end of the input. Assume it
+    // was meant to end with its line, and carry on reading from the next one.
+    fn unterminated_string(&mut self, token: Token) -> (Expr, Span) {
+        let line_end = token.text.find('\n').unwrap_or(token.text.len());
+        let span = Span {
+            end: token.span.start + line_end,
+            ..token.span
+        };
+        self.last_end = Span {
+            start: span.end,
+            end: span.end,
+            line: span.line,
+            column: span.column + token.text[..line_end].chars().count(),
+        };
+        if line_end < token.text.len() {
+            let resume = token.span.start + line_end + 1;
+            self.lexer = Lexer::starting_at(
+                self.source,
+                Span {
+                    start: resume,
+                    end: resume,
+                    line: token.span.line + 1,
+                    column: 1,
+                },
+            )
+            .with_readtable(self.readtable);
+        }
+        self.error_node("unterminated string literal".to_string(), span)
+    }
+}
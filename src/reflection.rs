@@ -0,0 +1,143 @@
+use crate::strings::expect_args;
+use crate::types::Expr;
+
+pub const BUILTINS: &[&str] = &[
+    "number?",
+    "real?",
+    "integer?",
+    "boolean?",
+    "procedure?",
+    "list?",
+    "null?",
+    "type-of",
+    "procedure-arity",
+    "procedure-name",
+    "procedure-source",
+];
+
+// Built-in type predicates and procedure reflection. Returns None when `name`
+// is not one of them. Predicates for the other types (string?, vector?, ...)
+// live next to the rest of that type's procedures.
+pub fn call(name: &str, args: &[Expr]) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "number?" | "real?" => {
+            predicate(name, args, |e| matches!(e, Expr::Number(_) | Expr::Real(_)))
+        }
+        // Reals with no fractional part count as integers, as in Scheme
+        "integer?" => predicate(name, args, |e| match e {
+            Expr::Number(_) => true,
+            Expr::Real(x) => x.is_finite() && x.fract() == 0.0,
+            _ => false,
+        }),
+        "boolean?" => predicate(name, args, |e| matches!(e, Expr::Bool(_))),
+        "procedure?" => predicate(name, args, |e| {
+            matches!(e, Expr::Lambda { .. } | Expr::Builtin(_))
+        }),
+        "list?" => predicate(name, args, |e| matches!(e, Expr::List(_))),
+        "null?" => predicate(
+            name,
+            args,
+            |e| matches!(e, Expr::List(items) if items.is_empty()),
+        ),
+        "type-of" => expect_args(name, args, 1).map(|_| Expr::Symbol(type_name(&args[0]).into())),
+        "procedure-arity" => procedure_arity(args),
+        "procedure-name" => procedure_name(args),
+        "procedure-source" => procedure_source(args),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn predicate(name: &str, args: &[Expr], test: fn(&Expr) -> bool) -> Result<Expr, String> {
+    expect_args(name, args, 1)?;
+    Ok(Expr::Bool(test(&args[0])))
+}
+
+// The symbol returned by type-of
+pub fn type_name(expr: &Expr) -> &'static str {
+    match expr {
+        Expr::Number(_) | Expr::Real(_) => "number",
+        Expr::Bool(_) => "boolean",
+        Expr::Symbol(_) => "symbol",
+        Expr::Keyword(_) => "keyword",
+        Expr::Str(_) => "string",
+        Expr::Char(_) => "char",
+        Expr::List(_) => "list",
+        Expr::Vector(_) => "vector",
+        Expr::Bytevector(_) => "bytevector",
+        Expr::HashTable(_) => "hash-table",
+        Expr::Map(_) => "hash-map",
+        Expr::Set(_) => "hash-set",
+        Expr::PVector(_) => "pvector",
+        Expr::Date(_) => "date",
+        Expr::Lambda { .. } | Expr::Builtin(_) => "procedure",
+        Expr::Unspecified => "unspecified",
+        Expr::Eof => "eof-object",
+        Expr::Error(_) => "error",
+    }
+}
+
+fn procedure_arg<'a>(name: &str, args: &'a [Expr]) -> Result<&'a Expr, String> {
+    expect_args(name, args, 1)?;
+    match &args[0] {
+        proc @ (Expr::Lambda { .. } | Expr::Builtin(_)) => Ok(proc),
+        other => Err(format!(
+            "{} requires a procedure, got {}",
+            name,
+            other.written()
+        )),
+    }
+}
+
+// The number of positional arguments a lambda takes, or #f for built-ins, which check
+// their own arguments and may accept a variable number
+fn procedure_arity(args: &[Expr]) -> Result<Expr, String> {
+    match procedure_arg("procedure-arity", args)? {
+        Expr::Lambda { params, .. } => Ok(Expr::Number(params.len() as i64)),
+        _ => Ok(Expr::Bool(false)),
+    }
+}
+
+// The built-in's name or the variable a lambda was defined as, or #f for an
+// anonymous lambda
+fn procedure_name(args: &[Expr]) -> Result<Expr, String> {
+    match procedure_arg("procedure-name", args)? {
+        Expr::Builtin(name) => Ok(Expr::Symbol((*name).into())),
+        Expr::Lambda {
+            name: Some(name), ..
+        } => Ok(Expr::Symbol(name.clone())),
+        _ => Ok(Expr::Bool(false)),
+    }
+}
+
+// The lambda expression a procedure was created from, as data:
+// (lambda (params ... [#:key keyword-params ...]) body). Built-ins have no
+// source, so they return #f.
+fn procedure_source(args: &[Expr]) -> Result<Expr, String> {
+    match procedure_arg("procedure-source", args)? {
+        Expr::Lambda {
+            params,
+            keywords,
+            body,
+            ..
+        } => {
+            let mut param_list: Vec<Expr> = params.iter().cloned().map(Expr::Symbol).collect();
+            if !keywords.is_empty() {
+                param_list.push(Expr::Keyword("key".into()));
+            }
+            for keyword in keywords {
+                let name = Expr::Symbol(keyword.name.clone());
+                param_list.push(match &keyword.default {
+                    Some(default) => Expr::List(vec![name, default.clone()]),
+                    None => name,
+                });
+            }
+            Ok(Expr::List(vec![
+                Expr::Symbol("lambda".into()),
+                Expr::List(param_list),
+                (**body).clone(),
+            ]))
+        }
+        _ => Ok(Expr::Bool(false)),
+    }
+}
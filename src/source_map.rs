@@ -0,0 +1,62 @@
+use crate::tokenizer::Span;
+use crate::types::Expr;
+use std::collections::HashMap;
+
+// Where the lists of a parsed program came from. Lists are keyed by the
+// address of their items, which stays put for as long as the parsed
+// expression is alive, so evaluation can find the span of the form it is
+// working on without the spans being stored in `Expr` itself. Once the
+// expression is dropped its addresses can be reused, so a map must not
+// outlive the code it describes: Env keeps only the map of the form being
+// run, and each lambda keeps the spans of its own body.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SourceMap {
+    spans: HashMap<usize, Span>,
+}
+
+impl SourceMap {
+    pub fn new() -> SourceMap {
+        SourceMap::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    // Empty lists all share one dangling address, so they are never recorded
+    pub(crate) fn record(&mut self, items: &[Expr], span: Span) {
+        if !items.is_empty() {
+            self.spans.insert(items.as_ptr() as usize, span);
+        }
+    }
+
+    // The span of a parsed list, given its items
+    pub fn span_of(&self, items: &[Expr]) -> Option<Span> {
+        if items.is_empty() {
+            return None;
+        }
+        self.spans.get(&(items.as_ptr() as usize)).copied()
+    }
+
+    pub fn extend(&mut self, other: SourceMap) {
+        self.spans.extend(other.spans);
+    }
+
+    // Records for the lists of `copy` the spans `source` has for the
+    // matching lists in `original`, so a cloned lambda body still points at
+    // its source
+    pub(crate) fn copy_spans(&mut self, source: &SourceMap, original: &Expr, copy: &Expr) {
+        if let (Expr::List(from), Expr::List(to)) = (original, copy) {
+            if let Some(span) = source.span_of(from) {
+                self.record(to, span);
+            }
+            for (a, b) in from.iter().zip(to.iter()) {
+                self.copy_spans(source, a, b);
+            }
+        }
+    }
+}
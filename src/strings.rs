@@ -0,0 +1,212 @@
+use crate::types::{Expr, format_real};
+
+pub const BUILTINS: &[&str] = &[
+    "string?",
+    "string-length",
+    "string-append",
+    "substring",
+    "string-ref",
+    "string=?",
+    "string<?",
+    "string>?",
+    "string<=?",
+    "string>=?",
+    "string->list",
+    "list->string",
+    "number->string",
+];
+
+// Built-in string procedures. Returns None when `name` is not one of them.
+// Indexes count characters, not bytes.
+pub fn call(name: &str, args: &[Expr]) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "string?" => {
+            expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::Str(_))))
+        }
+        "string-length" => string_length(args),
+        "string-append" => string_append(args),
+        "substring" => substring(args),
+        "string-ref" => string_ref(args),
+        "string=?" => compare_strings(name, args, |a, b| a == b),
+        "string<?" => compare_strings(name, args, |a, b| a < b),
+        "string>?" => compare_strings(name, args, |a, b| a > b),
+        "string<=?" => compare_strings(name, args, |a, b| a <= b),
+        "string>=?" => compare_strings(name, args, |a, b| a >= b),
+        "string->list" => string_to_list(args),
+        "list->string" => list_to_string(args),
+        "number->string" => number_to_string(args),
+        _ => return None,
+    };
+    Some(result)
+}
+
+pub(crate) fn expect_args(name: &str, args: &[Expr], count: usize) -> Result<(), String> {
+    if args.len() != count {
+        let noun = if count == 1 { "argument" } else { "arguments" };
+        return Err(format!("{} requires exactly {} {}", name, count, noun));
+    }
+    Ok(())
+}
+
+fn string_arg<'a>(name: &str, expr: &'a Expr) -> Result<&'a str, String> {
+    match expr {
+        Expr::Str(s) => Ok(s),
+        _ => Err(format!(
+            "{} requires a string, got {}",
+            name,
+            expr.written()
+        )),
+    }
+}
+
+fn index_arg(name: &str, expr: &Expr) -> Result<usize, String> {
+    match expr {
+        Expr::Number(n) if *n >= 0 => Ok(*n as usize),
+        _ => Err(format!(
+            "{} requires a non-negative index, got {}",
+            name, expr
+        )),
+    }
+}
+
+fn string_length(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("string-length", args, 1)?;
+    let s = string_arg("string-length", &args[0])?;
+    Ok(Expr::Number(s.chars().count() as i64))
+}
+
+fn string_append(args: &[Expr]) -> Result<Expr, String> {
+    let mut result = String::new();
+    for arg in args {
+        result.push_str(string_arg("string-append", arg)?);
+    }
+    Ok(Expr::Str(result.into()))
+}
+
+fn substring(args: &[Expr]) -> Result<Expr, String> {
+    if args.len() != 2 && args.len() != 3 {
+        return Err("substring requires 2 or 3 arguments".to_string());
+    }
+    let s = string_arg("substring", &args[0])?;
+    let len = s.chars().count();
+    let start = index_arg("substring", &args[1])?;
+    let end = match args.get(2) {
+        Some(e) => index_arg("substring", e)?,
+        None => len,
+    };
+    if start > end || end > len {
+        return Err(format!(
+            "substring range {}..{} out of bounds for string of length {}",
+            start, end, len
+        ));
+    }
+    let sub: String = s.chars().skip(start).take(end - start).collect();
+    Ok(Expr::Str(sub.into()))
+}
+
+fn string_ref(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("string-ref", args, 2)?;
+    let s = string_arg("string-ref", &args[0])?;
+    let k = index_arg("string-ref", &args[1])?;
+    match s.chars().nth(k) {
+        Some(c) => Ok(Expr::Char(c)),
+        None => Err(format!(
+            "string-ref index {} out of bounds for string of length {}",
+            k,
+            s.chars().count()
+        )),
+    }
+}
+
+fn compare_strings(name: &str, args: &[Expr], cmp: fn(&str, &str) -> bool) -> Result<Expr, String> {
+    if args.len() < 2 {
+        return Err(format!("{} requires at least 2 arguments", name));
+    }
+    let strings = args
+        .iter()
+        .map(|e| string_arg(name, e))
+        .collect::<Result<Vec<&str>, String>>()?;
+    Ok(Expr::Bool(strings.windows(2).all(|w| cmp(w[0], w[1]))))
+}
+
+fn string_to_list(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("string->list", args, 1)?;
+    let s = string_arg("string->list", &args[0])?;
+    Ok(Expr::List(s.chars().map(Expr::Char).collect()))
+}
+
+fn list_to_string(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("list->string", args, 1)?;
+    let items = match &args[0] {
+        Expr::List(items) => items,
+        other => {
+            return Err(format!(
+                "list->string requires a list, got {}",
+                other.written()
+            ));
+        }
+    };
+    let text = items
+        .iter()
+        .map(|e| match e {
+            Expr::Char(c) => Ok(*c),
+            _ => Err(format!(
+                "list->string requires characters, got {}",
+                e.written()
+            )),
+        })
+        .collect::<Result<String, String>>()?;
+    Ok(Expr::Str(text.into()))
+}
+
+fn number_to_string(args: &[Expr]) -> Result<Expr, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err("number->string requires 1 or 2 arguments".to_string());
+    }
+    let n = match (&args[0], args.get(1)) {
+        (Expr::Number(n), _) => *n,
+        // Reals are only written in decimal
+        (Expr::Real(x), None | Some(Expr::Number(10))) => {
+            return Ok(Expr::Str(format_real(*x).into()));
+        }
+        (Expr::Real(_), Some(radix)) => {
+            return Err(format!(
+                "number->string can only write reals in radix 10, got {}",
+                radix
+            ));
+        }
+        (other, _) => {
+            return Err(format!(
+                "number->string requires a number, got {}",
+                other.written()
+            ));
+        }
+    };
+    let radix = match args.get(1) {
+        None => 10,
+        Some(Expr::Number(r)) if (2..=36).contains(r) => *r as u32,
+        Some(other) => {
+            return Err(format!(
+                "number->string radix must be between 2 and 36, got {}",
+                other
+            ));
+        }
+    };
+    Ok(Expr::Str(format_radix(n, radix).into()))
+}
+
+pub(crate) fn format_radix(n: i64, radix: u32) -> String {
+    let mut magnitude = n.unsigned_abs();
+    if magnitude == 0 {
+        return "0".to_string();
+    }
+    let mut digits = Vec::new();
+    while magnitude > 0 {
+        digits.push(std::char::from_digit((magnitude % radix as u64) as u32, radix).unwrap());
+        magnitude /= radix as u64;
+    }
+    if n < 0 {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
@@ -0,0 +1,208 @@
+use crate::strings::expect_args;
+use crate::types::Expr;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::rc::Rc;
+
+pub const BUILTINS: &[&str] = &[
+    "symbol?",
+    "symbol->string",
+    "string->symbol",
+    "gensym",
+    "keyword?",
+    "keyword->string",
+    "string->keyword",
+];
+
+thread_local! {
+    // Every interned symbol name, so equal names share one allocation
+    static SYMBOLS: RefCell<HashSet<Rc<str>>> = RefCell::new(HashSet::new());
+    static GENSYM_COUNTER: Cell<u64> = const { Cell::new(0) };
+}
+
+// An interned symbol name.
+// Interning the same name twice yields the same allocation, so comparing and
+// hashing symbols only looks at the pointer, and cloning is a refcount bump.
+// Symbols made by `gensym` are never interned: they are distinct from every
+// other symbol, even one with the same name.
+#[derive(Clone)]
+pub struct Symbol(Rc<str>);
+
+impl Symbol {
+    pub fn intern(name: &str) -> Symbol {
+        SYMBOLS.with(|symbols| {
+            let mut symbols = symbols.borrow_mut();
+            if let Some(existing) = symbols.get(name) {
+                return Symbol(existing.clone());
+            }
+            let name: Rc<str> = name.into();
+            symbols.insert(name.clone());
+            Symbol(name)
+        })
+    }
+
+    // Creates a fresh uninterned symbol named `<prefix><counter>`
+    pub fn gensym(prefix: &str) -> Symbol {
+        let n = GENSYM_COUNTER.with(|counter| {
+            let n = counter.get() + 1;
+            counter.set(n);
+            n
+        });
+        Symbol(format!("{}{}", prefix, n).into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_interned(&self) -> bool {
+        SYMBOLS.with(|symbols| {
+            symbols
+                .borrow()
+                .get(self.as_str())
+                .is_some_and(|interned| Rc::ptr_eq(interned, &self.0))
+        })
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Symbol) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Rc::as_ptr(&self.0).cast::<u8>().hash(state)
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(name: &str) -> Symbol {
+        Symbol::intern(name)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(name: String) -> Symbol {
+        Symbol::intern(&name)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+// Built-in symbol procedures. Returns None when `name` is not one of them.
+pub fn call(name: &str, args: &[Expr]) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "symbol?" => {
+            expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::Symbol(_))))
+        }
+        "symbol->string" => symbol_to_string(args),
+        "string->symbol" => string_to_symbol(args),
+        "gensym" => gensym(args),
+        "keyword?" => {
+            expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::Keyword(_))))
+        }
+        "keyword->string" => keyword_to_string(args),
+        "string->keyword" => string_to_keyword(args),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn symbol_to_string(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("symbol->string", args, 1)?;
+    match &args[0] {
+        Expr::Symbol(s) => Ok(Expr::Str(s.as_str().into())),
+        other => Err(format!(
+            "symbol->string requires a symbol, got {}",
+            other.written()
+        )),
+    }
+}
+
+fn string_to_symbol(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("string->symbol", args, 1)?;
+    match &args[0] {
+        Expr::Str(s) => Ok(Expr::Symbol(Symbol::intern(s))),
+        other => Err(format!(
+            "string->symbol requires a string, got {}",
+            other.written()
+        )),
+    }
+}
+
+// (gensym [prefix]): a fresh symbol that is not eq? to any other symbol
+fn gensym(args: &[Expr]) -> Result<Expr, String> {
+    let prefix = match args {
+        [] => "g".to_string(),
+        [Expr::Str(s)] => s.to_string(),
+        [Expr::Symbol(s)] => s.to_string(),
+        [other] => {
+            return Err(format!(
+                "gensym prefix must be a string or symbol, got {}",
+                other.written()
+            ));
+        }
+        _ => return Err("gensym takes at most 1 argument".to_string()),
+    };
+    Ok(Expr::Symbol(Symbol::gensym(&prefix)))
+}
+
+// The keyword's name, without the leading colon
+fn keyword_to_string(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("keyword->string", args, 1)?;
+    match &args[0] {
+        Expr::Keyword(k) => Ok(Expr::Str(k.as_str().into())),
+        other => Err(format!(
+            "keyword->string requires a keyword, got {}",
+            other.written()
+        )),
+    }
+}
+
+fn string_to_keyword(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("string->keyword", args, 1)?;
+    match &args[0] {
+        Expr::Str(s) => Ok(Expr::Keyword(Symbol::intern(s))),
+        other => Err(format!(
+            "string->keyword requires a string, got {}",
+            other.written()
+        )),
+    }
+}
@@ -0,0 +1,406 @@
+use crate::strings::expect_args;
+use crate::types::Expr;
+use std::sync::OnceLock;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub const BUILTINS: &[&str] = &[
+    "current-time",
+    "current-jiffy",
+    "jiffies-per-second",
+    "current-date",
+    "make-date",
+    "date?",
+    "date-year",
+    "date-month",
+    "date-day",
+    "date-hour",
+    "date-minute",
+    "date-second",
+    "date-week-day",
+    "date->seconds",
+    "seconds->date",
+    "date->string",
+    "string->date",
+    "date-add",
+    "date-difference",
+];
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+// The years make-date and string->date accept. Far wider than any calendar
+// needs, and narrow enough that the day count cannot overflow.
+const MAX_YEAR: i64 = 1_000_000_000;
+
+// Jiffies are microseconds on the monotonic clock
+const JIFFIES_PER_SECOND: i64 = 1_000_000;
+
+// A moment in time, stored as whole seconds since 1970-01-01T00:00:00Z.
+// The calendar fields are always decomposed in UTC (proleptic Gregorian,
+// no leap seconds), so dates do not depend on the local time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Date {
+    seconds: i64,
+}
+
+// The calendar fields of a Date
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateFields {
+    pub year: i64,
+    pub month: i64,
+    pub day: i64,
+    pub hour: i64,
+    pub minute: i64,
+    pub second: i64,
+}
+
+impl Date {
+    pub fn from_seconds(seconds: i64) -> Date {
+        Date { seconds }
+    }
+
+    // The current time from the system clock
+    pub fn now() -> Date {
+        Date::from_seconds(unix_seconds())
+    }
+
+    // Builds a date from UTC calendar fields, rejecting out-of-range values
+    // such as February 30th or hour 24
+    pub fn from_fields(fields: DateFields) -> Result<Date, String> {
+        let DateFields {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        } = fields;
+        if !(-MAX_YEAR..=MAX_YEAR).contains(&year) {
+            return Err(format!(
+                "year {} out of range -{} to {}",
+                year, MAX_YEAR, MAX_YEAR
+            ));
+        }
+        if !(1..=12).contains(&month) {
+            return Err(format!("month {} out of range 1-12", month));
+        }
+        let max_day = days_in_month(year, month);
+        if !(1..=max_day).contains(&day) {
+            return Err(format!("day {} out of range 1-{}", day, max_day));
+        }
+        if !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0..60).contains(&second) {
+            return Err(format!(
+                "time {:02}:{:02}:{:02} out of range",
+                hour, minute, second
+            ));
+        }
+        days_from_civil(year, month, day)
+            .checked_mul(SECONDS_PER_DAY)
+            .and_then(|seconds| seconds.checked_add(hour * 3600 + minute * 60 + second))
+            .map(Date::from_seconds)
+            .ok_or_else(|| "date out of range".to_string())
+    }
+
+    pub fn seconds(self) -> i64 {
+        self.seconds
+    }
+
+    pub fn fields(self) -> DateFields {
+        let days = self.seconds.div_euclid(SECONDS_PER_DAY);
+        let time = self.seconds.rem_euclid(SECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        DateFields {
+            year,
+            month,
+            day,
+            hour: time / 3600,
+            minute: time % 3600 / 60,
+            second: time % 60,
+        }
+    }
+
+    // 0 for Sunday through 6 for Saturday
+    pub fn week_day(self) -> i64 {
+        // 1970-01-01 was a Thursday
+        (self.seconds.div_euclid(SECONDS_PER_DAY) + 4).rem_euclid(7)
+    }
+
+    // ISO-8601 in UTC, e.g. 2024-02-29T13:05:00Z. Years outside 0-9999 use
+    // the expanded form, a sign and at least five digits (e.g. -00044-03-15).
+    pub fn to_iso8601(self) -> String {
+        let f = self.fields();
+        let year = if (0..=9999).contains(&f.year) {
+            format!("{:04}", f.year)
+        } else {
+            format!("{:+06}", f.year)
+        };
+        format!(
+            "{}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year, f.month, f.day, f.hour, f.minute, f.second
+        )
+    }
+
+    // Parses an ISO-8601 date (YYYY-MM-DD, or ±YYYYY-MM-DD for other years)
+    // or date and time (YYYY-MM-DDTHH:MM[:SS[.fff]]) with an optional Z or
+    // +HH:MM/-HH:MM offset; times without an offset are taken as UTC.
+    // Fractions of a second are dropped.
+    pub fn parse_iso8601(text: &str) -> Result<Date, String> {
+        let invalid = || format!("invalid ISO-8601 date: {}", text);
+        let (date, time) = match text.split_once(['T', 't', ' ']) {
+            Some((date, time)) => (date, Some(time)),
+            None => (text, None),
+        };
+        let (year_sign, date) = match date.strip_prefix(['+', '-']) {
+            Some(rest) => (&date[..1], rest),
+            None => ("", date),
+        };
+        let mut date_parts = date.splitn(3, '-');
+        let year = year_field(year_sign, date_parts.next()).ok_or_else(invalid)?;
+        let month = number_field(date_parts.next(), 2).ok_or_else(invalid)?;
+        let day = number_field(date_parts.next(), 2).ok_or_else(invalid)?;
+
+        let (mut hour, mut minute, mut second, mut offset) = (0, 0, 0, 0);
+        if let Some(time) = time {
+            let (clock, zone) = match time.find(['Z', 'z', '+', '-']) {
+                Some(i) => time.split_at(i),
+                None => (time, ""),
+            };
+            let clock = match clock.split_once('.') {
+                Some((whole, fraction))
+                    if !fraction.is_empty() && fraction.bytes().all(|b| b.is_ascii_digit()) =>
+                {
+                    whole
+                }
+                Some(_) => return Err(invalid()),
+                None => clock,
+            };
+            let mut clock_parts = clock.split(':');
+            hour = number_field(clock_parts.next(), 2).ok_or_else(invalid)?;
+            minute = number_field(clock_parts.next(), 2).ok_or_else(invalid)?;
+            if let Some(part) = clock_parts.next() {
+                second = number_field(Some(part), 2).ok_or_else(invalid)?;
+            }
+            if clock_parts.next().is_some() {
+                return Err(invalid());
+            }
+            offset = match zone {
+                "" | "Z" | "z" => 0,
+                _ => {
+                    let sign = if zone.starts_with('-') { -1 } else { 1 };
+                    let (h, m) = zone[1..].split_once(':').ok_or_else(invalid)?;
+                    let h = number_field(Some(h), 2).ok_or_else(invalid)?;
+                    let m = number_field(Some(m), 2).ok_or_else(invalid)?;
+                    if h > 23 || m > 59 {
+                        return Err(invalid());
+                    }
+                    sign * (h * 3600 + m * 60)
+                }
+            };
+        }
+        let local = Date::from_fields(DateFields {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+        .map_err(|err| format!("{}: {}", invalid(), err))?;
+        Ok(Date::from_seconds(local.seconds - offset))
+    }
+}
+
+// A field of exactly `digits` ASCII digits
+// An ISO-8601 year: four digits, or a sign and five or more digits
+fn year_field(sign: &str, part: Option<&str>) -> Option<i64> {
+    let part = part?;
+    if sign.is_empty() {
+        return number_field(Some(part), 4);
+    }
+    if part.len() < 5 || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let year: i64 = part.parse().ok()?;
+    Some(if sign == "-" { -year } else { year })
+}
+
+fn number_field(part: Option<&str>, digits: usize) -> Option<i64> {
+    let part = part?;
+    if part.len() != digits || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    part.parse().ok()
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 for a proleptic Gregorian date. Counts from March so
+// the leap day falls at the end of each 400-year era's years.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (month + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// The inverse of days_from_civil: (year, month, day)
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn unix_seconds() -> i64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => elapsed.as_secs() as i64,
+        Err(before) => -(before.duration().as_secs() as i64),
+    }
+}
+
+// Microseconds since the first call, from the monotonic clock, which never
+// goes backwards even if the system clock is adjusted
+fn current_jiffy() -> i64 {
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_micros() as i64
+}
+
+// Built-in date and time procedures. Returns None when `name` is not one of
+// them. Durations are integer numbers of seconds.
+pub fn call(name: &str, args: &[Expr]) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "current-time" => expect_args(name, args, 0).map(|_| Expr::Number(unix_seconds())),
+        "current-jiffy" => expect_args(name, args, 0).map(|_| Expr::Number(current_jiffy())),
+        "jiffies-per-second" => {
+            expect_args(name, args, 0).map(|_| Expr::Number(JIFFIES_PER_SECOND))
+        }
+        "current-date" => expect_args(name, args, 0).map(|_| Expr::Date(Date::now())),
+        "make-date" => make_date(args),
+        "date?" => expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::Date(_)))),
+        "date-year" => date_field(name, args, |f| f.year),
+        "date-month" => date_field(name, args, |f| f.month),
+        "date-day" => date_field(name, args, |f| f.day),
+        "date-hour" => date_field(name, args, |f| f.hour),
+        "date-minute" => date_field(name, args, |f| f.minute),
+        "date-second" => date_field(name, args, |f| f.second),
+        "date-week-day" => date_arg_only(name, args).map(|d| Expr::Number(d.week_day())),
+        "date->seconds" => date_arg_only(name, args).map(|d| Expr::Number(d.seconds())),
+        "seconds->date" => expect_args(name, args, 1)
+            .and_then(|_| number_arg(name, &args[0]))
+            .map(|n| Expr::Date(Date::from_seconds(n))),
+        "date->string" => date_arg_only(name, args).map(|d| Expr::Str(d.to_iso8601().into())),
+        "string->date" => string_to_date(args),
+        "date-add" => date_add(args),
+        "date-difference" => date_difference(args),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn number_arg(name: &str, expr: &Expr) -> Result<i64, String> {
+    match expr {
+        Expr::Number(n) => Ok(*n),
+        other => Err(format!(
+            "{} requires an integer, got {}",
+            name,
+            other.written()
+        )),
+    }
+}
+
+fn date_arg(name: &str, expr: &Expr) -> Result<Date, String> {
+    match expr {
+        Expr::Date(date) => Ok(*date),
+        other => Err(format!("{} requires a date, got {}", name, other.written())),
+    }
+}
+
+fn date_arg_only(name: &str, args: &[Expr]) -> Result<Date, String> {
+    expect_args(name, args, 1)?;
+    date_arg(name, &args[0])
+}
+
+fn date_field(name: &str, args: &[Expr], field: fn(&DateFields) -> i64) -> Result<Expr, String> {
+    date_arg_only(name, args).map(|d| Expr::Number(field(&d.fields())))
+}
+
+// (make-date year month day [hour [minute [second]]]), in UTC
+fn make_date(args: &[Expr]) -> Result<Expr, String> {
+    if args.len() < 3 || args.len() > 6 {
+        return Err("make-date requires 3 to 6 arguments".to_string());
+    }
+    let mut values = [0; 6];
+    for (value, arg) in values.iter_mut().zip(args) {
+        *value = number_arg("make-date", arg)?;
+    }
+    let [year, month, day, hour, minute, second] = values;
+    Date::from_fields(DateFields {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+    })
+    .map(Expr::Date)
+    .map_err(|err| format!("make-date: {}", err))
+}
+
+fn string_to_date(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("string->date", args, 1)?;
+    match &args[0] {
+        Expr::Str(text) => Date::parse_iso8601(text)
+            .map(Expr::Date)
+            .map_err(|err| format!("string->date: {}", err)),
+        other => Err(format!(
+            "string->date requires a string, got {}",
+            other.written()
+        )),
+    }
+}
+
+// (date-add date seconds): the date `seconds` later (earlier if negative)
+fn date_add(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("date-add", args, 2)?;
+    let date = date_arg("date-add", &args[0])?;
+    let seconds = number_arg("date-add", &args[1])?;
+    date.seconds()
+        .checked_add(seconds)
+        .map(|s| Expr::Date(Date::from_seconds(s)))
+        .ok_or_else(|| "date-add: date out of range".to_string())
+}
+
+// (date-difference a b): the seconds from b to a
+fn date_difference(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("date-difference", args, 2)?;
+    let a = date_arg("date-difference", &args[0])?;
+    let b = date_arg("date-difference", &args[1])?;
+    a.seconds()
+        .checked_sub(b.seconds())
+        .map(Expr::Number)
+        .ok_or_else(|| "date-difference: difference out of range".to_string())
+}
@@ -1,24 +1,576 @@
+use crate::numbers::parse_number;
+use crate::readtable::Readtable;
+
+// A region of the source text: the byte offsets `start..end`, plus the line
+// and column (both 1-based, columns counted in characters) where it starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    LParen,
+    RParen,
+    // #( opening a vector literal
+    VectorOpen,
+    // #u8( opening a bytevector literal
+    BytevectorOpen,
+    // ' before a datum, short for (quote datum), or another prefix character
+    // of the readtable
+    Quote,
+    // A string literal, quotes and escapes included; it may be unterminated
+    String,
+    // A character literal such as #\a or #\space
+    Char,
+    // Anything else: numbers, booleans, symbols and keywords
+    Atom,
+    // Text that can't start any token, such as an unterminated #| comment;
+    // the parser reports it
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    Whitespace,
+    // ; up to the end of the line
+    LineComment,
+    // #| ... |#, nested comments included
+    BlockComment,
+    // #; and the datum it comments out
+    DatumComment,
+}
+
+// Source text between tokens, which the lexer skips
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trivia<'a> {
+    pub kind: TriviaKind,
+    pub text: &'a str,
+    pub span: Span,
+}
+
+// A token and where it came from. `text` borrows from the source.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    pub span: Span,
+}
+
+impl Token<'_> {
+    // The position just past the token, as an empty span
+    pub fn end_position(&self) -> Span {
+        let (line, column) = match self.text.rfind('\n') {
+            Some(i) => (
+                self.span.line + self.text.matches('\n').count(),
+                self.text[i + 1..].chars().count() + 1,
+            ),
+            None => (self.span.line, self.span.column + self.text.chars().count()),
+        };
+        Span {
+            start: self.span.end,
+            end: self.span.end,
+            line,
+            column,
+        }
+    }
+
+    // Whether the input ended before the token did: a string with no closing
+    // quote, or a block comment with no closing |#
+    pub fn is_unterminated(&self) -> bool {
+        match self.kind {
+            TokenKind::String => {
+                let mut chars = self.text[1..].chars();
+                while let Some(c) = chars.next() {
+                    if c == '\\' {
+                        chars.next();
+                    } else if c == '"' {
+                        return false;
+                    }
+                }
+                true
+            }
+            TokenKind::Error => self.text.starts_with("#|"),
+            _ => false,
+        }
+    }
+}
+
+// Copies every token; `tokenize_slices` and `Lexer` borrow from the source
+// instead
 pub fn tokenize(raw: &str) -> Vec<String> {
-    let mut tokens: Vec<String> = Vec::new();
-    let mut buffer = String::new();
-    for ch in raw.chars() {
-        if ch == '(' || ch == ')' {
-            if !buffer.is_empty() {
-                tokens.push(buffer.clone());
-                buffer.clear();
-            }
-            tokens.push(ch.to_string());
-        } else if ch == ' ' || ch == '\t' || ch == '\r' || ch == '\n' {
-            if !buffer.is_empty() {
-                tokens.push(buffer.clone());
-                buffer.clear();
+    Lexer::new(raw)
+        .map(|token| token.text.to_string())
+        .collect()
+}
+
+pub fn tokenize_spanned(source: &str) -> Vec<Token<'_>> {
+    Lexer::new(source).collect()
+}
+
+// The text of each token, as slices of `source`, read as they are needed
+pub fn tokenize_slices(source: &str) -> impl Iterator<Item = &str> {
+    Lexer::new(source).map(|token| token.text)
+}
+
+// The kind of a token given only its text, for tokens that did not come
+// from the lexer
+pub(crate) fn kind_of(text: &str) -> TokenKind {
+    match text {
+        "(" => TokenKind::LParen,
+        ")" => TokenKind::RParen,
+        "#(" => TokenKind::VectorOpen,
+        "#u8(" => TokenKind::BytevectorOpen,
+        "'" => TokenKind::Quote,
+        _ if text.starts_with('"') => TokenKind::String,
+        _ if text.starts_with("#\\") => TokenKind::Char,
+        _ if text.starts_with("#|") => TokenKind::Error,
+        // Something that starts like a number but isn't one, such as 12abc
+        _ if matches!(parse_number(text), Some(Err(_))) => TokenKind::Error,
+        _ => TokenKind::Atom,
+    }
+}
+
+fn is_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\r' || c == '\n'
+}
+
+// The message for an Error token
+pub(crate) fn lex_error(text: &str) -> String {
+    if text.starts_with("#|") {
+        "unterminated block comment: missing '|#'".to_string()
+    } else if let Some(Err(message)) = parse_number(text) {
+        message
+    } else {
+        format!("unexpected characters: {}", text)
+    }
+}
+
+// Splits source text into tokens, tracking line and column as it goes.
+// Comments are skipped: ; to the end of the line, #| ... |# (which nest) and
+// #; which comments out the datum after it.
+// Tokenizing never fails: malformed literals (such as an unterminated string)
+// are returned as tokens and reported by the parser. The tokens borrow from
+// the source ('a); the readtable ('r) only has to outlive the lexer.
+pub struct Lexer<'a, 'r> {
+    source: &'a str,
+    pos: usize,
+    line: usize,
+    column: usize,
+    // A token read while skipping a #; datum that turned out not to be part of it
+    pending: Option<Token<'a>>,
+    // Set when the input ends part way through a #; datum: where the #; is,
+    // and how many lists the datum had opened
+    unfinished_comment: Option<(Span, usize)>,
+    // The reader macros to recognise; None is the default table
+    readtable: Option<&'r Readtable>,
+}
+
+impl<'a, 'r> Lexer<'a, 'r> {
+    pub fn new(source: &'a str) -> Self {
+        Lexer::starting_at(
+            source,
+            Span {
+                start: 0,
+                end: 0,
+                line: 1,
+                column: 1,
+            },
+        )
+    }
+
+    // A lexer that picks up at `position` (as given by `Token::end_position`)
+    // part way through `source`
+    pub fn starting_at(source: &'a str, position: Span) -> Self {
+        Lexer {
+            source,
+            pos: position.start,
+            line: position.line,
+            column: position.column,
+            pending: None,
+            unfinished_comment: None,
+            readtable: None,
+        }
+    }
+
+    // Whether the input ended inside the datum of a #; comment, and if so
+    // where the #; is and how many lists the datum left open. More input
+    // would continue the datum.
+    pub(crate) fn unfinished_comment(&self) -> Option<(Span, usize)> {
+        self.unfinished_comment
+    }
+
+    // Reads the prefix characters of `readtable` as tokens of their own, and
+    // skips the datum after its dispatch macros along with them under #;
+    pub fn with_readtable(mut self, readtable: &'r Readtable) -> Self {
+        self.readtable = Some(readtable);
+        self
+    }
+
+    fn is_prefix_char(&self, c: char) -> bool {
+        match self.readtable {
+            Some(readtable) => readtable.prefix_chars().contains(&c),
+            None => c == '\'',
+        }
+    }
+
+    fn is_dispatch(&self, text: &str) -> bool {
+        self.readtable
+            .is_some_and(|readtable| readtable.contains(text))
+    }
+
+    // Where the next token or trivia will start, as an empty span
+    pub(crate) fn position(&self) -> Span {
+        Span {
+            start: self.pos,
+            end: self.pos,
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    // The rest of a string literal after its opening quote
+    fn string_body(&mut self) {
+        while let Some(c) = self.bump() {
+            if c == '\\' {
+                self.bump();
+            } else if c == '"' {
+                break;
             }
+        }
+    }
+
+    // Skips whitespace and comments up to the next token. If the input ends
+    // inside a block comment, returns an Error token for the comment.
+    fn skip_trivia(&mut self) -> Option<Token<'a>> {
+        loop {
+            if self.peek().is_some_and(is_whitespace) {
+                self.bump();
+            } else if self.rest().starts_with(';') {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.bump();
+                }
+            } else if self.rest().starts_with("#|") {
+                let (start, line, column) = (self.pos, self.line, self.column);
+                if !self.block_comment() {
+                    return Some(Token {
+                        kind: TokenKind::Error,
+                        text: &self.source[start..],
+                        span: Span {
+                            start,
+                            end: self.pos,
+                            line,
+                            column,
+                        },
+                    });
+                }
+            } else {
+                return None;
+            }
+        }
+    }
+
+    // Skips a #| ... |# comment, including any comments nested in it.
+    // Returns false if it is never closed.
+    fn block_comment(&mut self) -> bool {
+        let mut depth = 0;
+        loop {
+            if self.rest().starts_with("#|") {
+                depth += 1;
+                self.bump();
+                self.bump();
+            } else if self.rest().starts_with("|#") {
+                depth -= 1;
+                self.bump();
+                self.bump();
+                if depth == 0 {
+                    return true;
+                }
+            } else if self.bump().is_none() {
+                return false;
+            }
+        }
+    }
+
+    // Reads the whitespace run or comment at the current position, if any
+    pub(crate) fn next_trivia(&mut self) -> Option<Trivia<'a>> {
+        let (start, line, column) = (self.pos, self.line, self.column);
+        let kind = if self.peek().is_some_and(is_whitespace) {
+            while self.peek().is_some_and(is_whitespace) {
+                self.bump();
+            }
+            TriviaKind::Whitespace
+        } else if self.rest().starts_with(';') {
+            while self.peek().is_some_and(|c| c != '\n') {
+                self.bump();
+            }
+            TriviaKind::LineComment
+        } else if self.rest().starts_with("#|") {
+            self.block_comment();
+            TriviaKind::BlockComment
+        } else if self.rest().starts_with("#;") {
+            self.bump();
+            self.bump();
+            self.skip_datum(Span {
+                start,
+                end: start + 2,
+                line,
+                column,
+            });
+            // A ) read while looking for the datum is not part of the comment
+            if let Some(close) = self.pending.take() {
+                self.pos = close.span.start;
+                self.line = close.span.line;
+                self.column = close.span.column;
+            }
+            TriviaKind::DatumComment
         } else {
-            buffer.push(ch);
+            return None;
+        };
+        Some(Trivia {
+            kind,
+            text: &self.source[start..self.pos],
+            span: Span {
+                start,
+                end: self.pos,
+                line,
+                column,
+            },
+        })
+    }
+
+    // Skips the datum after the #; at `span`, which may itself be a list,
+    // quoted, or commented out. Each #; before it adds another datum to skip,
+    // as #; #; a b skips both. This loops rather than recursing, so no input
+    // can exhaust the stack. If the input ends before the datum does, the #;
+    // is recorded along with how many lists the datum had opened.
+    fn skip_datum(&mut self, span: Span) {
+        let mut datums = 1;
+        let mut depth = 0;
+        while datums > 0 {
+            if let Some(unterminated) = self.skip_trivia() {
+                self.pending = Some(unterminated);
+                return;
+            }
+            if self.rest().starts_with("#;") {
+                self.bump();
+                self.bump();
+                if depth == 0 {
+                    datums += 1;
+                }
+                continue;
+            }
+            let token = match self.token() {
+                Some(token) if !token.is_unterminated() => token,
+                _ => {
+                    self.unfinished_comment = Some((span, depth));
+                    return;
+                }
+            };
+            match token.kind {
+                TokenKind::LParen | TokenKind::VectorOpen | TokenKind::BytevectorOpen => depth += 1,
+                // Nothing to comment out: the ) still closes its list
+                TokenKind::RParen | TokenKind::Error if depth == 0 => {
+                    self.pending = Some(token);
+                    return;
+                }
+                TokenKind::RParen => depth -= 1,
+                // A prefix belongs to the datum after it
+                TokenKind::Quote => continue,
+                TokenKind::Atom if self.is_dispatch(token.text) => continue,
+                _ => {}
+            }
+            if depth == 0 {
+                datums -= 1;
+            }
         }
     }
-    if !buffer.is_empty() {
-        tokens.push(buffer.clone());
+
+    // Reads the token at the current position, which is not trivia
+    fn token(&mut self) -> Option<Token<'a>> {
+        let (start, line, column) = (self.pos, self.line, self.column);
+        let kind = match self.bump()? {
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            c if self.is_prefix_char(c) => TokenKind::Quote,
+            '"' => {
+                // String literal: keep the raw text (quotes and escapes included)
+                // as a single token, so spaces and parens inside it are not split.
+                // The escapes are decoded by `read_string_literal`.
+                self.string_body();
+                TokenKind::String
+            }
+            _ => self.atom_body(start),
+        };
+        Some(Token {
+            kind,
+            text: &self.source[start..self.pos],
+            span: Span {
+                start,
+                end: self.pos,
+                line,
+                column,
+            },
+        })
+    }
+
+    // The rest of an atom whose first character has been consumed
+    fn atom_body(&mut self, start: usize) -> TokenKind {
+        loop {
+            let text = &self.source[start..self.pos];
+            if text == "#\\" {
+                // Character literal: the character after #\ is taken as-is,
+                // so #\( #\) and #\  are not treated as delimiters
+                match self.bump() {
+                    // A delimiter can't start a character name, so the literal ends here
+                    Some(c) if c.is_whitespace() || c == '(' || c == ')' => return TokenKind::Char,
+                    Some(_) => continue,
+                    None => return TokenKind::Char,
+                }
+            }
+            match self.peek() {
+                // Vector literal #( ... ) or bytevector literal #u8( ... )
+                Some('(') if text == "#" => {
+                    self.bump();
+                    return TokenKind::VectorOpen;
+                }
+                Some('(') if text == "#u8" => {
+                    self.bump();
+                    return TokenKind::BytevectorOpen;
+                }
+                Some(c) if !is_whitespace(c) && !matches!(c, '(' | ')' | '"' | ';') => {
+                    self.bump();
+                }
+                // A dispatch macro name is an atom even if it looks like a number
+                _ if self.is_dispatch(text) => return TokenKind::Atom,
+                _ => return kind_of(text),
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a, '_> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        loop {
+            if let Some(token) = self.pending.take() {
+                return Some(token);
+            }
+            if let Some(unterminated) = self.skip_trivia() {
+                return Some(unterminated);
+            }
+            if !self.rest().starts_with("#;") {
+                return self.token();
+            }
+            let span = Span {
+                start: self.pos,
+                end: self.pos + 2,
+                line: self.line,
+                column: self.column,
+            };
+            self.bump();
+            self.bump();
+            self.skip_datum(span);
+        }
+    }
+}
+
+// Decodes a string literal token produced by `tokenize`, e.g. "a\tb" (with quotes)
+// Supported escapes: \n \t \r \" \\ and \x<hex>; (a Unicode scalar value)
+pub fn read_string_literal(token: &str) -> Result<String, String> {
+    let inner = token
+        .strip_prefix('"')
+        .ok_or(format!("not a string literal: {}", token))?;
+    let mut text = String::new();
+    let mut chars = inner.chars();
+    loop {
+        match chars.next() {
+            None => return Err("unterminated string literal".to_string()),
+            Some('"') => break,
+            Some('\\') => match chars.next() {
+                Some('n') => text.push('\n'),
+                Some('t') => text.push('\t'),
+                Some('r') => text.push('\r'),
+                Some('"') => text.push('"'),
+                Some('\\') => text.push('\\'),
+                Some('x') => {
+                    let mut hex = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(';') => break,
+                            Some(c) => hex.push(c),
+                            None => return Err("unterminated \\x escape in string".to_string()),
+                        }
+                    }
+                    let ch = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or(format!("invalid \\x escape in string: \\x{};", hex))?;
+                    text.push(ch);
+                }
+                Some(c) => return Err(format!("unknown escape in string: \\{}", c)),
+                None => return Err("unterminated string literal".to_string()),
+            },
+            Some(c) => text.push(c),
+        }
+    }
+    if chars.next().is_some() {
+        return Err(format!(
+            "unexpected characters after string literal: {}",
+            token
+        ));
+    }
+    Ok(text)
+}
+
+// Decodes a character literal token such as #\a, #\space or #\x3bb
+pub fn read_char_literal(token: &str) -> Result<char, String> {
+    let body = token
+        .strip_prefix("#\\")
+        .ok_or(format!("not a character literal: {}", token))?;
+    let mut chars = body.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => Err("empty character literal".to_string()),
+        (Some(c), None) => Ok(c),
+        _ => match body {
+            "space" => Ok(' '),
+            "newline" => Ok('\n'),
+            "tab" => Ok('\t'),
+            "return" => Ok('\r'),
+            "null" => Ok('\0'),
+            "alarm" => Ok('\u{7}'),
+            "backspace" => Ok('\u{8}'),
+            "delete" => Ok('\u{7f}'),
+            "escape" => Ok('\u{1b}'),
+            _ => body
+                .strip_prefix('x')
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .and_then(char::from_u32)
+                .ok_or(format!("unknown character name: {}", token)),
+        },
     }
-    tokens
 }
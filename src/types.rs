@@ -1,38 +1,231 @@
 use crate::env::Env;
+use crate::hash_tables::HashTable;
+use crate::persistent_map::{PersistentMap, PersistentSet};
+use crate::persistent_vector::PersistentVector;
+use crate::source_map::SourceMap;
+use crate::symbol::Symbol;
+use crate::time::Date;
+use std::cell::RefCell;
 use std::fmt;
+use std::rc::Rc;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Expr {
-    Symbol(String),
-    Number(i32),
+    Symbol(Symbol),
+    // A self-evaluating name written :name or #:name (stored without the
+    // prefix), used to pass named arguments
+    Keyword(Symbol),
+    Number(i64),
+    // An inexact (floating-point) number
+    Real(f64),
     List(Vec<Expr>),
     Bool(bool),
+    Str(Rc<str>),
+    Char(char),
+    Vector(Rc<RefCell<Vec<Expr>>>),
+    Bytevector(Rc<RefCell<Vec<u8>>>),
+    HashTable(Rc<RefCell<HashTable>>),
+    // Immutable collections: "modifying" one returns a new value that shares
+    // structure with the original
+    Map(PersistentMap),
+    Set(PersistentSet),
+    PVector(PersistentVector),
+    Date(Date),
+    Builtin(&'static str),
+    // The result of forms evaluated only for their effect (define, set!,
+    // vector-set!, ...); the top-level printer does not show it
+    Unspecified,
+    // The end-of-file object returned by readers at end of input
+    Eof,
+    // Stands in for text the error-recovering parser could not read;
+    // evaluating it fails with the message
+    Error(Rc<str>),
     Lambda {
-        params: Vec<String>,
-        body: Box<Expr>,
+        params: Vec<Symbol>,
+        // Named parameters declared after #:key, passed as `:name value`
+        keywords: Vec<KeywordParam>,
+        body: Rc<Expr>,
+        // Where the lists of `body` came from
+        spans: Rc<SourceMap>,
         closure_env: Env,
+        // The variable the lambda was first bound to by define, if any
+        name: Option<Symbol>,
     },
 }
 
+// A lambda parameter declared after #:key, as `name` or `(name default)`
+#[derive(Debug, PartialEq, Clone)]
+pub struct KeywordParam {
+    pub name: Symbol,
+    // Evaluated at call time when the caller leaves the keyword out; a
+    // parameter without a default must always be passed
+    pub default: Option<Expr>,
+}
+
+impl Expr {
+    // The `write` representation: like Display, but strings and characters
+    // are printed in literal syntax so the output can be read back by the parser
+    pub fn written(&self) -> Written<'_> {
+        Written(self)
+    }
+}
+
+pub struct Written<'a>(&'a Expr);
+
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Expr::Number(n) => write!(f, "{}", n),
-            Expr::Bool(b) => write!(f, "{}", b),
-            Expr::Symbol(s) => write!(f, "{}", s),
-            Expr::List(items) => {
-                write!(f, "(")?;
-                for (i, item) in items.iter().enumerate() {
-                    if i > 0 {
-                        write!(f, " ")?;
-                    }
-                    write!(f, "{}", item)?;
+        fmt_expr(self, f, false, &mut Vec::new())
+    }
+}
+
+impl fmt::Display for Written<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_expr(self.0, f, true, &mut Vec::new())
+    }
+}
+
+// `inside` holds the vectors being printed, by address. A vector that
+// contains itself (through vector-set!) prints as #<cycle> where it recurs.
+fn fmt_expr(
+    expr: &Expr,
+    f: &mut fmt::Formatter,
+    write: bool,
+    inside: &mut Vec<*const ()>,
+) -> fmt::Result {
+    // Only the collections recurse; everything else is printed by fmt_atom,
+    // which keeps this function's stack frame small for deeply nested values
+    match expr {
+        Expr::List(items) => fmt_items("(", items.iter(), ")", f, write, inside),
+        Expr::Vector(items) => {
+            let address = Rc::as_ptr(items).cast();
+            if inside.contains(&address) {
+                return write!(f, "#<cycle>");
+            }
+            inside.push(address);
+            let result = fmt_items("#(", items.borrow().iter(), ")", f, write, inside);
+            inside.pop();
+            result
+        }
+        Expr::Map(map) => {
+            write!(f, "{{")?;
+            for (i, (key, value)) in map.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
                 }
-                write!(f, ")")
+                fmt_expr(key, f, write, inside)?;
+                write!(f, " ")?;
+                fmt_expr(value, f, write, inside)?;
             }
-            Expr::Lambda { params, .. } => {
-                write!(f, "<function({})>", params.join(", "))
+            write!(f, "}}")
+        }
+        Expr::Set(set) => fmt_items("#{", set.iter(), "}", f, write, inside),
+        Expr::PVector(items) => fmt_items("[", items.iter(), "]", f, write, inside),
+        _ => fmt_atom(expr, f, write),
+    }
+}
+
+fn fmt_atom(expr: &Expr, f: &mut fmt::Formatter, write: bool) -> fmt::Result {
+    match expr {
+        Expr::Number(n) => write!(f, "{}", n),
+        Expr::Real(x) => write!(f, "{}", format_real(*x)),
+        Expr::Bool(true) => write!(f, "#t"),
+        Expr::Bool(false) => write!(f, "#f"),
+        Expr::Symbol(s) => write!(f, "{}", s),
+        Expr::Keyword(k) => write!(f, ":{}", k),
+        Expr::Str(s) if write => write_string_literal(s, f),
+        Expr::Str(s) => write!(f, "{}", s),
+        Expr::Char(c) if write => write_char_literal(*c, f),
+        Expr::Char(c) => write!(f, "{}", c),
+        Expr::Bytevector(bytes) => {
+            write!(f, "#u8(")?;
+            for (i, byte) in bytes.borrow().iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", byte)?;
             }
+            write!(f, ")")
+        }
+        Expr::HashTable(table) => write!(f, "#<hash-table {}>", table.borrow().len()),
+        Expr::Date(date) => write!(f, "#<date {}>", date.to_iso8601()),
+        Expr::Builtin(name) => write!(f, "<builtin {}>", name),
+        Expr::Unspecified => write!(f, "#<unspecified>"),
+        Expr::Eof => write!(f, "#<eof>"),
+        Expr::Error(message) => write!(f, "#<error {}>", message),
+        Expr::Lambda {
+            params, keywords, ..
+        } => {
+            let names: Vec<String> = params
+                .iter()
+                .map(|p| p.to_string())
+                .chain(keywords.iter().map(|k| format!(":{}", k.name)))
+                .collect();
+            write!(f, "<function({})>", names.join(", "))
+        }
+        Expr::List(_) | Expr::Vector(_) | Expr::Map(_) | Expr::Set(_) | Expr::PVector(_) => {
+            fmt_expr(expr, f, write, &mut Vec::new())
+        }
+    }
+}
+
+// Reals always show a decimal point or exponent, so they read back as reals:
+// 2.0, 0.1, 1e100, +inf.0, +nan.0
+pub fn format_real(x: f64) -> String {
+    if x.is_nan() {
+        "+nan.0".to_string()
+    } else if x.is_infinite() {
+        if x > 0.0 { "+inf.0" } else { "-inf.0" }.to_string()
+    } else {
+        format!("{:?}", x)
+    }
+}
+
+fn fmt_items<'a>(
+    open: &str,
+    items: impl Iterator<Item = &'a Expr>,
+    close: &str,
+    f: &mut fmt::Formatter,
+    write: bool,
+    inside: &mut Vec<*const ()>,
+) -> fmt::Result {
+    write!(f, "{}", open)?;
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
         }
+        fmt_expr(item, f, write, inside)?;
+    }
+    write!(f, "{}", close)
+}
+
+fn write_string_literal(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "\"")?;
+    for ch in s.chars() {
+        match ch {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\t' => write!(f, "\\t")?,
+            '\r' => write!(f, "\\r")?,
+            c if c.is_control() => write!(f, "\\x{:x};", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+fn write_char_literal(c: char, f: &mut fmt::Formatter) -> fmt::Result {
+    match c {
+        ' ' => write!(f, "#\\space"),
+        '\n' => write!(f, "#\\newline"),
+        '\t' => write!(f, "#\\tab"),
+        '\r' => write!(f, "#\\return"),
+        '\0' => write!(f, "#\\null"),
+        '\u{7}' => write!(f, "#\\alarm"),
+        '\u{8}' => write!(f, "#\\backspace"),
+        '\u{7f}' => write!(f, "#\\delete"),
+        '\u{1b}' => write!(f, "#\\escape"),
+        c if c.is_control() || c.is_whitespace() => write!(f, "#\\x{:x}", c as u32),
+        c => write!(f, "#\\{}", c),
     }
 }
@@ -0,0 +1,211 @@
+use crate::env::Env;
+use crate::eval::apply;
+use crate::strings::expect_args;
+use crate::types::Expr;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub const BUILTINS: &[&str] = &[
+    "vector?",
+    "make-vector",
+    "vector",
+    "vector-length",
+    "vector-ref",
+    "vector-set!",
+    "vector-fill!",
+    "vector->list",
+    "list->vector",
+    "vector-map",
+];
+
+// Built-in vector procedures. Returns None when `name` is not one of them.
+// Vectors are shared and mutable: every copy of an Expr::Vector refers to the
+// same storage, so vector-set! is visible through all of them.
+pub fn call(name: &str, args: &[Expr], env: &mut Env) -> Option<Result<Expr, String>> {
+    let result = match name {
+        "vector?" => {
+            expect_args(name, args, 1).map(|_| Expr::Bool(matches!(args[0], Expr::Vector(_))))
+        }
+        "make-vector" => make_vector(args),
+        "vector" => Ok(new_vector(args.to_vec())),
+        "vector-length" => vector_length(args),
+        "vector-ref" => vector_ref(args),
+        "vector-set!" => vector_set(args),
+        "vector-fill!" => vector_fill(args),
+        "vector->list" => vector_to_list(args),
+        "list->vector" => list_to_vector(args),
+        "vector-map" => vector_map(args, env),
+        _ => return None,
+    };
+    Some(result)
+}
+
+pub fn new_vector(items: Vec<Expr>) -> Expr {
+    Expr::Vector(Rc::new(RefCell::new(items)))
+}
+
+fn vector_arg<'a>(name: &str, expr: &'a Expr) -> Result<&'a Rc<RefCell<Vec<Expr>>>, String> {
+    match expr {
+        Expr::Vector(items) => Ok(items),
+        _ => Err(format!(
+            "{} requires a vector, got {}",
+            name,
+            expr.written()
+        )),
+    }
+}
+
+// Checks that `expr` is a valid index into a vector or bytevector of length
+// `len` (`len` itself is allowed when `inclusive`, for the end of a range)
+pub(crate) fn index_arg(
+    name: &str,
+    expr: &Expr,
+    len: usize,
+    inclusive: bool,
+) -> Result<usize, String> {
+    let index = match expr {
+        Expr::Number(n) => *n,
+        _ => {
+            return Err(format!(
+                "{} requires an integer index, got {}",
+                name,
+                expr.written()
+            ));
+        }
+    };
+    let limit = if inclusive { len + 1 } else { len };
+    if index < 0 || index as usize >= limit {
+        return Err(format!(
+            "{}: index {} out of range for length {}",
+            name, index, len
+        ));
+    }
+    Ok(index as usize)
+}
+
+// Optional [start [end]] arguments starting at args[from]
+pub(crate) fn range_args(
+    name: &str,
+    args: &[Expr],
+    from: usize,
+    len: usize,
+) -> Result<(usize, usize), String> {
+    if args.len() > from + 2 {
+        return Err(format!("{} takes at most {} arguments", name, from + 2));
+    }
+    let start = match args.get(from) {
+        Some(e) => index_arg(name, e, len, true)?,
+        None => 0,
+    };
+    let end = match args.get(from + 1) {
+        Some(e) => index_arg(name, e, len, true)?,
+        None => len,
+    };
+    if start > end {
+        return Err(format!("{}: start {} is after end {}", name, start, end));
+    }
+    Ok((start, end))
+}
+
+// The longest vector or bytevector make-vector and make-bytevector will
+// allocate, so that a mistaken length is an error rather than a process
+// aborted for lack of memory
+const MAX_LENGTH: usize = 1 << 24;
+
+pub(crate) fn length_arg(name: &str, expr: &Expr) -> Result<usize, String> {
+    match expr {
+        Expr::Number(n) if *n >= 0 && *n as u64 <= MAX_LENGTH as u64 => Ok(*n as usize),
+        Expr::Number(n) if *n >= 0 => Err(format!(
+            "{}: length {} is more than the maximum of {}",
+            name, n, MAX_LENGTH
+        )),
+        _ => Err(format!(
+            "{} requires a non-negative length, got {}",
+            name,
+            expr.written()
+        )),
+    }
+}
+
+fn make_vector(args: &[Expr]) -> Result<Expr, String> {
+    if args.is_empty() || args.len() > 2 {
+        return Err("make-vector requires 1 or 2 arguments".to_string());
+    }
+    let len = length_arg("make-vector", &args[0])?;
+    let fill = args.get(1).cloned().unwrap_or(Expr::Number(0));
+    Ok(new_vector(vec![fill; len]))
+}
+
+fn vector_length(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("vector-length", args, 1)?;
+    let items = vector_arg("vector-length", &args[0])?;
+    Ok(Expr::Number(items.borrow().len() as i64))
+}
+
+fn vector_ref(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("vector-ref", args, 2)?;
+    let items = vector_arg("vector-ref", &args[0])?.borrow();
+    let k = index_arg("vector-ref", &args[1], items.len(), false)?;
+    Ok(items[k].clone())
+}
+
+fn vector_set(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("vector-set!", args, 3)?;
+    let mut items = vector_arg("vector-set!", &args[0])?.borrow_mut();
+    let k = index_arg("vector-set!", &args[1], items.len(), false)?;
+    items[k] = args[2].clone();
+    Ok(Expr::Unspecified)
+}
+
+fn vector_fill(args: &[Expr]) -> Result<Expr, String> {
+    if args.len() < 2 {
+        return Err("vector-fill! requires at least 2 arguments".to_string());
+    }
+    let vector = vector_arg("vector-fill!", &args[0])?;
+    let mut items = vector.borrow_mut();
+    let (start, end) = range_args("vector-fill!", args, 2, items.len())?;
+    for item in &mut items[start..end] {
+        *item = args[1].clone();
+    }
+    Ok(Expr::Unspecified)
+}
+
+fn vector_to_list(args: &[Expr]) -> Result<Expr, String> {
+    if args.is_empty() {
+        return Err("vector->list requires at least 1 argument".to_string());
+    }
+    let items = vector_arg("vector->list", &args[0])?.borrow();
+    let (start, end) = range_args("vector->list", args, 1, items.len())?;
+    Ok(Expr::List(items[start..end].to_vec()))
+}
+
+fn list_to_vector(args: &[Expr]) -> Result<Expr, String> {
+    expect_args("list->vector", args, 1)?;
+    match &args[0] {
+        Expr::List(items) => Ok(new_vector(items.clone())),
+        other => Err(format!(
+            "list->vector requires a list, got {}",
+            other.written()
+        )),
+    }
+}
+
+// (vector-map proc v1 v2 ...): applies proc element-wise, stopping at the
+// shortest vector
+fn vector_map(args: &[Expr], env: &mut Env) -> Result<Expr, String> {
+    if args.len() < 2 {
+        return Err("vector-map requires a procedure and at least 1 vector".to_string());
+    }
+    // Snapshot the inputs so `proc` may safely mutate them while we iterate
+    let vectors = args[1..]
+        .iter()
+        .map(|v| vector_arg("vector-map", v).map(|items| items.borrow().clone()))
+        .collect::<Result<Vec<Vec<Expr>>, String>>()?;
+    let len = vectors.iter().map(|v| v.len()).min().unwrap_or(0);
+    let mut result = Vec::with_capacity(len);
+    for i in 0..len {
+        let call_args = vectors.iter().map(|v| v[i].clone()).collect();
+        result.push(apply(&args[0], call_args, env)?);
+    }
+    Ok(new_vector(result))
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_tokenize_bytevector_literal() {
+    assert_eq!(tokenize("#u8(1 2)"), vec!["#u8(", "1", "2", ")"]);
+}
+
+#[test]
+fn test_bytevector_literal() {
+    let mut env = default_env();
+    let result = run("#u8(1 2 255)", &mut env).unwrap();
+    assert_eq!(result.to_string(), "#u8(1 2 255)");
+}
+
+#[test]
+fn test_bytevector_literal_needs_bytes() {
+    let mut env = default_env();
+    assert!(run("#u8(256)", &mut env).is_err());
+    assert!(run("#u8(a)", &mut env).is_err());
+}
+
+#[test]
+fn test_bytevector_display_round_trips() {
+    let mut env = default_env();
+    let original = run("(bytevector 0 127 255)", &mut env).unwrap();
+    let text = original.to_string();
+    let reparsed = run(&text, &mut env).unwrap();
+    assert_eq!(reparsed, original);
+}
+
+#[test]
+fn test_make_bytevector_and_length() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(make-bytevector 3 7)", &mut env).unwrap().to_string(),
+        "#u8(7 7 7)"
+    );
+    assert_eq!(
+        run("(bytevector-length (make-bytevector 4))", &mut env).unwrap(),
+        Expr::Number(4)
+    );
+}
+
+#[test]
+fn test_make_bytevector_fill_must_be_a_byte() {
+    assert!(run("(make-bytevector 2 300)", &mut default_env()).is_err());
+}
+
+#[test]
+fn test_make_bytevector_length_limit() {
+    let result = run("(make-bytevector 2000000000)", &mut default_env());
+    assert!(result.unwrap_err().contains("is more than the maximum"));
+}
+
+#[test]
+fn test_u8_ref_and_set() {
+    let mut env = default_env();
+    run("(define frame (make-bytevector 2 0))", &mut env).unwrap();
+    run("(bytevector-u8-set! frame 1 171)", &mut env).unwrap();
+    assert_eq!(
+        run("(bytevector-u8-ref frame 1)", &mut env).unwrap(),
+        Expr::Number(171)
+    );
+}
+
+#[test]
+fn test_u8_errors() {
+    let mut env = default_env();
+    run("(define frame (make-bytevector 2 0))", &mut env).unwrap();
+    assert_eq!(
+        run("(bytevector-u8-ref frame 2)", &mut env).unwrap_err(),
+        "bytevector-u8-ref: index 2 out of range for length 2"
+    );
+    assert!(run("(bytevector-u8-set! frame 0 -1)", &mut env).is_err());
+}
+
+#[test]
+fn test_copy_is_independent() {
+    let mut env = default_env();
+    run("(define a #u8(1 2 3 4))", &mut env).unwrap();
+    run("(define b (bytevector-copy a 1 3))", &mut env).unwrap();
+    run("(bytevector-u8-set! b 0 9)", &mut env).unwrap();
+    assert_eq!(run("a", &mut env).unwrap().to_string(), "#u8(1 2 3 4)");
+    assert_eq!(run("b", &mut env).unwrap().to_string(), "#u8(9 3)");
+}
+
+#[test]
+fn test_append() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(bytevector-append #u8(1) #u8() #u8(2 3))", &mut env)
+            .unwrap()
+            .to_string(),
+        "#u8(1 2 3)"
+    );
+}
+
+#[test]
+fn test_utf8_conversion() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(string->utf8 \"\\x3bb;a\")", &mut env)
+            .unwrap()
+            .to_string(),
+        "#u8(206 187 97)"
+    );
+    assert_eq!(
+        run("(utf8->string #u8(206 187 97))", &mut env).unwrap(),
+        Expr::Str("λa".into())
+    );
+    assert_eq!(
+        run("(utf8->string #u8(104 105 33) 0 2)", &mut env).unwrap(),
+        Expr::Str("hi".into())
+    );
+}
+
+#[test]
+fn test_invalid_utf8() {
+    assert!(run("(utf8->string #u8(255))", &mut default_env()).is_err());
+}
+
+#[test]
+fn test_bytevector_equality() {
+    let mut env = default_env();
+    run("(define a #u8(1 2))", &mut env).unwrap();
+    assert_eq!(run("(eqv? a a)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(
+        run("(eqv? a (bytevector 1 2))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(equal? a (bytevector 1 2))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
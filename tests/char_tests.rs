@@ -0,0 +1,126 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_tokenize_char_literals() {
+    assert_eq!(
+        tokenize("(f #\\a #\\()"),
+        vec!["(", "f", "#\\a", "#\\(", ")"]
+    );
+    assert_eq!(tokenize("#\\ #\\)"), vec!["#\\ ", "#\\)"]);
+    assert_eq!(tokenize("(#\\space)"), vec!["(", "#\\space", ")"]);
+}
+
+#[test]
+fn test_read_char_literals() {
+    assert_eq!(read_char_literal("#\\a").unwrap(), 'a');
+    assert_eq!(read_char_literal("#\\x").unwrap(), 'x');
+    assert_eq!(read_char_literal("#\\space").unwrap(), ' ');
+    assert_eq!(read_char_literal("#\\newline").unwrap(), '\n');
+    assert_eq!(read_char_literal("#\\x3bb").unwrap(), 'λ');
+    assert_eq!(read_char_literal("#\\λ").unwrap(), 'λ');
+}
+
+#[test]
+fn test_read_invalid_char_literals() {
+    assert!(read_char_literal("#\\bogus").is_err());
+    assert!(read_char_literal("#\\xd800").is_err());
+}
+
+#[test]
+fn test_char_is_self_evaluating() {
+    let mut env = default_env();
+    assert_eq!(run("#\\z", &mut env).unwrap(), Expr::Char('z'));
+    assert_eq!(run("#\\(", &mut env).unwrap(), Expr::Char('('));
+}
+
+#[test]
+fn test_char_to_integer() {
+    let result = run("(char->integer #\\A)", &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Number(65));
+}
+
+#[test]
+fn test_integer_to_char() {
+    let result = run("(integer->char 955)", &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Char('λ'));
+}
+
+#[test]
+fn test_integer_to_char_rejects_non_scalar_values() {
+    let mut env = default_env();
+    assert!(run("(integer->char 55296)", &mut env).is_err());
+    assert!(run("(integer->char -1)", &mut env).is_err());
+}
+
+#[test]
+fn test_char_classification_is_unicode_aware() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(char-alphabetic? #\\x3bb)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(char-alphabetic? #\\1)", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(char-numeric? #\\x664)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(char-whitespace? #\\space)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(char-whitespace? #\\x3000)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_char_case_mapping() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(char-upcase #\\a)", &mut env).unwrap(),
+        Expr::Char('A')
+    );
+    assert_eq!(
+        run("(char-downcase #\\x3a3)", &mut env).unwrap(),
+        Expr::Char('σ')
+    );
+    // ß upcases to "SS", which is not a single character
+    assert_eq!(
+        run("(char-upcase #\\ß)", &mut env).unwrap(),
+        Expr::Char('ß')
+    );
+}
+
+#[test]
+fn test_char_comparisons() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(char<? #\\a #\\b #\\c)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(char=? #\\a #\\a #\\b)", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_char_comparison_type_error() {
+    assert!(run("(char<? #\\a 1)", &mut default_env()).is_err());
+}
+
+#[test]
+fn test_char_write_representation() {
+    assert_eq!(Expr::Char('a').to_string(), "a");
+    assert_eq!(Expr::Char('a').written().to_string(), "#\\a");
+    assert_eq!(Expr::Char(' ').written().to_string(), "#\\space");
+    assert_eq!(Expr::Char('\n').written().to_string(), "#\\newline");
+    assert_eq!(Expr::Char('\u{3000}').written().to_string(), "#\\x3000");
+}
@@ -0,0 +1,55 @@
+use std::fs;
+use std::process::Command;
+
+// Runs the interpreter on `source` as a file, returning its standard output
+// and standard error
+fn run_file(name: &str, source: &str) -> (String, String) {
+    let path = std::env::temp_dir().join(format!("cli_tests_{}.lisp", name));
+    fs::write(&path, source).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_lisp_interpreter_rs"))
+        .arg(&path)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+    (
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn test_prints_each_result() {
+    let (stdout, stderr) = run_file("results", "(define x 2)\n(* x 21)\n\"text\"\n");
+    assert_eq!(stdout, "42\n\"text\"\n");
+    assert_eq!(stderr, "");
+}
+
+#[test]
+fn test_reader_macro_defined_in_the_file() {
+    // Read with the default readtable, #even would be an undefined variable
+    let source = "(set-reader-macro! \"#even\" (lambda (n) (* 2 n)))\n(+ #even 4 1)\n";
+    let (stdout, stderr) = run_file("reader_macro", source);
+    assert_eq!(stdout, "9\n");
+    assert_eq!(stderr, "");
+}
+
+#[test]
+fn test_parse_error_stops_the_program() {
+    let source = "(+ 1 2)\n(+ 1 \"a\\q\")\n)\n(+ 1\n";
+    let (stdout, stderr) = run_file("parse_error", source);
+    assert_eq!(stdout, "3\n");
+    assert_eq!(
+        stderr,
+        "Parse error at line 2, column 6: unknown escape in string: \\q\n"
+    );
+}
+
+#[test]
+fn test_dispatch_macro_that_starts_like_a_number_prefix() {
+    // #d is the decimal prefix, so #date used to lex as a malformed number
+    let source =
+        "(set-reader-macro! \"#date\" (lambda (s) (string-length s)))\n(+ 1 #date\"2024-03-05\")\n";
+    let (stdout, stderr) = run_file("date_macro", source);
+    assert_eq!(stdout, "11\n");
+    assert_eq!(stderr, "");
+}
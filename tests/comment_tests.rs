@@ -0,0 +1,78 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_inline_line_comment() {
+    let mut env = Env::new();
+    assert_eq!(run("(+ 1 2) ; sum", &mut env).unwrap(), Expr::Number(3));
+    assert_eq!(tokenize("(+ 1 ; one\n 2)"), vec!["(", "+", "1", "2", ")"]);
+}
+
+#[test]
+fn test_semicolon_ends_an_atom() {
+    assert_eq!(tokenize("abc;comment"), vec!["abc"]);
+    // Inside strings and character literals ; is just a character
+    assert_eq!(tokenize("\"a;b\" #\\;"), vec!["\"a;b\"", "#\\;"]);
+}
+
+#[test]
+fn test_block_comment() {
+    let mut env = Env::new();
+    assert_eq!(
+        run("(+ 1 #| two\nlines |# 2)", &mut env).unwrap(),
+        Expr::Number(3)
+    );
+    assert_eq!(tokenize("#|a|#b"), vec!["b"]);
+}
+
+#[test]
+fn test_nested_block_comment() {
+    assert_eq!(
+        tokenize("(x #| outer #| inner |# still outer |# y)"),
+        vec!["(", "x", "y", ")"]
+    );
+}
+
+#[test]
+fn test_unterminated_block_comment() {
+    let err = parse_program("(define x 1)\n  #| never #| closed |#").unwrap_err();
+    assert_eq!(err.message, "unterminated block comment: missing '|#'");
+    assert_eq!((err.span.line, err.span.column), (2, 3));
+}
+
+#[test]
+fn test_datum_comment() {
+    let mut env = Env::new();
+    assert_eq!(
+        run("(+ 1 #;(* 100 (- 2 1)) 2)", &mut env).unwrap(),
+        Expr::Number(3)
+    );
+    assert_eq!(tokenize("#;x y"), vec!["y"]);
+    assert_eq!(tokenize("#;'(a b) c"), vec!["c"]);
+    assert_eq!(tokenize("#;#(1 2) c"), vec!["c"]);
+}
+
+#[test]
+fn test_stacked_datum_comments() {
+    // Each #; skips one datum, including one that follows another #;
+    assert_eq!(tokenize("#; #; a b c"), vec!["c"]);
+}
+
+#[test]
+fn test_datum_comment_before_close_paren() {
+    // There is no datum to skip, so the list still closes
+    assert_eq!(tokenize("(a #;)"), vec!["(", "a", ")"]);
+}
+
+#[test]
+fn test_comments_in_program() {
+    let source = "; setup\n(define x 10) ; ten\n#| (define x 20) |#\n#;(define x 30)\n(* x 2)";
+    let program = parse_program(source).unwrap();
+    assert_eq!(program.forms.len(), 2);
+    let mut env = Env::new();
+    eval(&program.forms[0], &mut env).unwrap();
+    assert_eq!(eval(&program.forms[1], &mut env).unwrap(), Expr::Number(20));
+    assert_eq!((program.spans[1].line, program.spans[1].column), (5, 1));
+}
@@ -0,0 +1,9 @@
+use lisp_interpreter_rs::*;
+
+// Tokenizes, parses and evaluates a single form in `env`
+pub fn run(input: &str, env: &mut Env) -> Result<Expr, String> {
+    let tokens = tokenize(input);
+    let token_refs: Vec<&str> = tokens.iter().map(|s| s.as_str()).collect();
+    let expr = parse(&token_refs)?;
+    eval(&expr, env)
+}
@@ -5,7 +5,7 @@ use lisp_interpreter_rs::*;
 fn test_less_than_true() {
     // (< 3 5) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol("<".to_string()),
+        Expr::Symbol("<".into()),
         Expr::Number(3),
         Expr::Number(5),
     ]);
@@ -17,7 +17,7 @@ fn test_less_than_true() {
 fn test_less_than_false() {
     // (< 5 3) should return false
     let expr = Expr::List(vec![
-        Expr::Symbol("<".to_string()),
+        Expr::Symbol("<".into()),
         Expr::Number(5),
         Expr::Number(3),
     ]);
@@ -29,7 +29,7 @@ fn test_less_than_false() {
 fn test_less_than_equal_false() {
     // (< 5 5) should return false
     let expr = Expr::List(vec![
-        Expr::Symbol("<".to_string()),
+        Expr::Symbol("<".into()),
         Expr::Number(5),
         Expr::Number(5),
     ]);
@@ -42,7 +42,7 @@ fn test_less_than_equal_false() {
 fn test_greater_than_true() {
     // (> 5 3) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol(">".to_string()),
+        Expr::Symbol(">".into()),
         Expr::Number(5),
         Expr::Number(3),
     ]);
@@ -54,7 +54,7 @@ fn test_greater_than_true() {
 fn test_greater_than_false() {
     // (> 3 5) should return false
     let expr = Expr::List(vec![
-        Expr::Symbol(">".to_string()),
+        Expr::Symbol(">".into()),
         Expr::Number(3),
         Expr::Number(5),
     ]);
@@ -66,7 +66,7 @@ fn test_greater_than_false() {
 fn test_greater_than_equal_false() {
     // (> 5 5) should return false
     let expr = Expr::List(vec![
-        Expr::Symbol(">".to_string()),
+        Expr::Symbol(">".into()),
         Expr::Number(5),
         Expr::Number(5),
     ]);
@@ -79,7 +79,7 @@ fn test_greater_than_equal_false() {
 fn test_less_equal_true_less() {
     // (<= 3 5) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol("<=".to_string()),
+        Expr::Symbol("<=".into()),
         Expr::Number(3),
         Expr::Number(5),
     ]);
@@ -91,7 +91,7 @@ fn test_less_equal_true_less() {
 fn test_less_equal_true_equal() {
     // (<= 5 5) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol("<=".to_string()),
+        Expr::Symbol("<=".into()),
         Expr::Number(5),
         Expr::Number(5),
     ]);
@@ -103,7 +103,7 @@ fn test_less_equal_true_equal() {
 fn test_less_equal_false() {
     // (<= 5 3) should return false
     let expr = Expr::List(vec![
-        Expr::Symbol("<=".to_string()),
+        Expr::Symbol("<=".into()),
         Expr::Number(5),
         Expr::Number(3),
     ]);
@@ -116,7 +116,7 @@ fn test_less_equal_false() {
 fn test_greater_equal_true_greater() {
     // (>= 5 3) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol(">=".to_string()),
+        Expr::Symbol(">=".into()),
         Expr::Number(5),
         Expr::Number(3),
     ]);
@@ -128,7 +128,7 @@ fn test_greater_equal_true_greater() {
 fn test_greater_equal_true_equal() {
     // (>= 5 5) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol(">=".to_string()),
+        Expr::Symbol(">=".into()),
         Expr::Number(5),
         Expr::Number(5),
     ]);
@@ -140,7 +140,7 @@ fn test_greater_equal_true_equal() {
 fn test_greater_equal_false() {
     // (>= 3 5) should return false
     let expr = Expr::List(vec![
-        Expr::Symbol(">=".to_string()),
+        Expr::Symbol(">=".into()),
         Expr::Number(3),
         Expr::Number(5),
     ]);
@@ -153,7 +153,7 @@ fn test_greater_equal_false() {
 fn test_equal_true() {
     // (= 5 5) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol("=".to_string()),
+        Expr::Symbol("=".into()),
         Expr::Number(5),
         Expr::Number(5),
     ]);
@@ -165,7 +165,7 @@ fn test_equal_true() {
 fn test_equal_false() {
     // (= 5 3) should return false
     let expr = Expr::List(vec![
-        Expr::Symbol("=".to_string()),
+        Expr::Symbol("=".into()),
         Expr::Number(5),
         Expr::Number(3),
     ]);
@@ -177,7 +177,7 @@ fn test_equal_false() {
 fn test_equal_negative_numbers() {
     // (= -5 -5) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol("=".to_string()),
+        Expr::Symbol("=".into()),
         Expr::Number(-5),
         Expr::Number(-5),
     ]);
@@ -190,9 +190,9 @@ fn test_equal_negative_numbers() {
 fn test_comparison_with_expression() {
     // (< (+ 1 2) 5) should return true (3 < 5)
     let expr = Expr::List(vec![
-        Expr::Symbol("<".to_string()),
+        Expr::Symbol("<".into()),
         Expr::List(vec![
-            Expr::Symbol("+".to_string()),
+            Expr::Symbol("+".into()),
             Expr::Number(1),
             Expr::Number(2),
         ]),
@@ -206,14 +206,14 @@ fn test_comparison_with_expression() {
 fn test_comparison_both_expressions() {
     // (= (+ 2 3) (* 1 5)) should return true (5 = 5)
     let expr = Expr::List(vec![
-        Expr::Symbol("=".to_string()),
+        Expr::Symbol("=".into()),
         Expr::List(vec![
-            Expr::Symbol("+".to_string()),
+            Expr::Symbol("+".into()),
             Expr::Number(2),
             Expr::Number(3),
         ]),
         Expr::List(vec![
-            Expr::Symbol("*".to_string()),
+            Expr::Symbol("*".into()),
             Expr::Number(1),
             Expr::Number(5),
         ]),
@@ -227,9 +227,9 @@ fn test_comparison_both_expressions() {
 fn test_comparison_with_if() {
     // (if (< 3 5) 100 200) should return 100
     let expr = Expr::List(vec![
-        Expr::Symbol("if".to_string()),
+        Expr::Symbol("if".into()),
         Expr::List(vec![
-            Expr::Symbol("<".to_string()),
+            Expr::Symbol("<".into()),
             Expr::Number(3),
             Expr::Number(5),
         ]),
@@ -244,9 +244,9 @@ fn test_comparison_with_if() {
 fn test_comparison_with_if_false() {
     // (if (> 3 5) 100 200) should return 200
     let expr = Expr::List(vec![
-        Expr::Symbol("if".to_string()),
+        Expr::Symbol("if".into()),
         Expr::List(vec![
-            Expr::Symbol(">".to_string()),
+            Expr::Symbol(">".into()),
             Expr::Number(3),
             Expr::Number(5),
         ]),
@@ -261,14 +261,14 @@ fn test_comparison_with_if_false() {
 #[test]
 fn test_comparison_with_variables() {
     let mut env = default_env();
-    env.insert("x".to_string(), Expr::Number(10));
-    env.insert("y".to_string(), Expr::Number(20));
+    env.insert("x".into(), Expr::Number(10));
+    env.insert("y".into(), Expr::Number(20));
 
     // (< x y) should return true (10 < 20)
     let expr = Expr::List(vec![
-        Expr::Symbol("<".to_string()),
-        Expr::Symbol("x".to_string()),
-        Expr::Symbol("y".to_string()),
+        Expr::Symbol("<".into()),
+        Expr::Symbol("x".into()),
+        Expr::Symbol("y".into()),
     ]);
     let result = eval(&expr, &mut env).unwrap();
     assert_eq!(result, Expr::Bool(true));
@@ -279,7 +279,7 @@ fn test_comparison_with_variables() {
 fn test_comparison_with_zero() {
     // (> 5 0) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol(">".to_string()),
+        Expr::Symbol(">".into()),
         Expr::Number(5),
         Expr::Number(0),
     ]);
@@ -291,7 +291,7 @@ fn test_comparison_with_zero() {
 fn test_comparison_negative_numbers() {
     // (< -10 -5) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol("<".to_string()),
+        Expr::Symbol("<".into()),
         Expr::Number(-10),
         Expr::Number(-5),
     ]);
@@ -303,22 +303,64 @@ fn test_comparison_negative_numbers() {
 #[test]
 fn test_comparison_insufficient_args() {
     // (< 5) should error
-    let expr = Expr::List(vec![Expr::Symbol("<".to_string()), Expr::Number(5)]);
+    let expr = Expr::List(vec![Expr::Symbol("<".into()), Expr::Number(5)]);
     let result = eval(&expr, &mut default_env());
     assert!(result.is_err());
 }
 
+// Chained comparisons
 #[test]
-fn test_comparison_too_many_args() {
-    // (< 1 2 3) should error
+fn test_comparison_chained_true() {
+    // (< 1 2 3) should return true
     let expr = Expr::List(vec![
-        Expr::Symbol("<".to_string()),
+        Expr::Symbol("<".into()),
         Expr::Number(1),
         Expr::Number(2),
         Expr::Number(3),
     ]);
+    let result = eval(&expr, &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Bool(true));
+}
+
+#[test]
+fn test_comparison_chained_false() {
+    // (< 1 3 2) should return false
+    let expr = Expr::List(vec![
+        Expr::Symbol("<".into()),
+        Expr::Number(1),
+        Expr::Number(3),
+        Expr::Number(2),
+    ]);
+    let result = eval(&expr, &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Bool(false));
+}
+
+#[test]
+fn test_comparison_chained_non_strict() {
+    // (<= 1 1 2 2) and (= 4 4 4) should return true, (>= 3 3 4) false
+    let cases = [
+        ("<=", vec![1, 1, 2, 2], true),
+        ("=", vec![4, 4, 4], true),
+        (">=", vec![3, 3, 4], false),
+    ];
+    for (op, nums, expected) in cases {
+        let mut items = vec![Expr::Symbol(op.into())];
+        items.extend(nums.into_iter().map(Expr::Number));
+        let result = eval(&Expr::List(items), &mut default_env()).unwrap();
+        assert_eq!(result, Expr::Bool(expected));
+    }
+}
+
+#[test]
+fn test_comparison_requires_numbers() {
+    // (= 1 true) should error
+    let expr = Expr::List(vec![
+        Expr::Symbol("=".into()),
+        Expr::Number(1),
+        Expr::Bool(true),
+    ]);
     let result = eval(&expr, &mut default_env());
-    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), "= requires numbers");
 }
 
 // Complex nested comparison
@@ -326,20 +368,20 @@ fn test_comparison_too_many_args() {
 fn test_nested_comparison_with_if() {
     // (if (= (+ 2 3) 5) (if (< 1 2) 42 0) 99) should return 42
     let expr = Expr::List(vec![
-        Expr::Symbol("if".to_string()),
+        Expr::Symbol("if".into()),
         Expr::List(vec![
-            Expr::Symbol("=".to_string()),
+            Expr::Symbol("=".into()),
             Expr::List(vec![
-                Expr::Symbol("+".to_string()),
+                Expr::Symbol("+".into()),
                 Expr::Number(2),
                 Expr::Number(3),
             ]),
             Expr::Number(5),
         ]),
         Expr::List(vec![
-            Expr::Symbol("if".to_string()),
+            Expr::Symbol("if".into()),
             Expr::List(vec![
-                Expr::Symbol("<".to_string()),
+                Expr::Symbol("<".into()),
                 Expr::Number(1),
                 Expr::Number(2),
             ]),
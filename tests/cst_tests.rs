@@ -0,0 +1,155 @@
+use lisp_interpreter_rs::*;
+
+fn kinds(trivia: &[Trivia]) -> Vec<TriviaKind> {
+    trivia.iter().map(|t| t.kind).collect()
+}
+
+#[test]
+fn test_round_trip_is_byte_for_byte() {
+    let sources = [
+        "",
+        "   \n",
+        "(define (square x) ; squares\n  (* x x))\n",
+        "#| header\n #| nested |# |#\r\n(+ 1 #;(ignored 2) 2)\t; done",
+        "'(a . \"b c\" #\\( #\\space) #(1 2) #u8(3)   ",
+        "λ (café \"ünïcode\")",
+        include_str!("../example.lisp"),
+    ];
+    for source in sources {
+        assert_eq!(parse_cst(source).to_string(), source);
+    }
+}
+
+#[test]
+fn test_round_trip_of_malformed_input() {
+    let sources = [
+        "(unclosed (list  ; comment\n",
+        "extra ) parens))",
+        "\"open string (",
+        "(a #| open comment",
+        "'",
+        "(a #;",
+    ];
+    for source in sources {
+        assert_eq!(parse_cst(source).to_string(), source);
+    }
+}
+
+#[test]
+fn test_trivia_is_attached_to_the_next_node() {
+    let tree = parse_cst("; first\n(a) #| between |# b ; last\n");
+    assert_eq!(tree.forms.len(), 2);
+    assert_eq!(
+        kinds(tree.forms[0].leading()),
+        vec![TriviaKind::LineComment, TriviaKind::Whitespace]
+    );
+    assert_eq!(
+        kinds(tree.forms[1].leading()),
+        vec![
+            TriviaKind::Whitespace,
+            TriviaKind::BlockComment,
+            TriviaKind::Whitespace
+        ]
+    );
+    assert_eq!(
+        kinds(&tree.trailing),
+        vec![
+            TriviaKind::Whitespace,
+            TriviaKind::LineComment,
+            TriviaKind::Whitespace
+        ]
+    );
+    assert_eq!(tree.trailing[1].text, "; last");
+}
+
+#[test]
+fn test_datum_comment_trivia() {
+    let tree = parse_cst("(f #;(g (h)) x #;)");
+    let SyntaxNode::List {
+        items, trailing, ..
+    } = &tree.forms[0]
+    else {
+        panic!("expected a list");
+    };
+    let datum_comment = items[1].leading()[1];
+    assert_eq!(datum_comment.kind, TriviaKind::DatumComment);
+    assert_eq!(datum_comment.text, "#;(g (h))");
+    // Nothing follows the last #;, so it comments out nothing and the list closes
+    assert_eq!(trailing[1].text, "#;");
+}
+
+#[test]
+fn test_node_structure_and_spans() {
+    let tree = parse_cst("  '(a\n  #(b))");
+    let SyntaxNode::Quote { datum, .. } = &tree.forms[0] else {
+        panic!("expected a quote");
+    };
+    let Some(datum) = datum else {
+        panic!("expected a datum");
+    };
+    let SyntaxNode::List { open, items, .. } = datum.as_ref() else {
+        panic!("expected a list");
+    };
+    assert_eq!(open.kind, TokenKind::LParen);
+    let span = items[1].span();
+    assert_eq!(
+        (span.start, span.end, span.line, span.column),
+        (8, 12, 2, 3)
+    );
+    assert_eq!(tree.forms[0].span().start, 2);
+}
+
+#[test]
+fn test_lower_to_expressions() {
+    let source = "; setup\n(define x 4) #| note |#\n(* x x)";
+    let tree = parse_cst(source);
+    let program = tree.lower().unwrap();
+    assert_eq!(program.forms, parse_program(source).unwrap().forms);
+    let mut env = Env::new();
+    let mut result = Expr::Unspecified;
+    for form in &program.forms {
+        result = eval(form, &mut env).unwrap();
+    }
+    assert_eq!(result, Expr::Number(16));
+    assert_eq!(
+        tree.forms[1].lower().unwrap(),
+        Expr::List(vec![
+            Expr::Symbol("*".into()),
+            Expr::Symbol("x".into()),
+            Expr::Symbol("x".into()),
+        ])
+    );
+}
+
+#[test]
+fn test_lower_reports_problems() {
+    let err = parse_cst("(ok)\n(not closed").lower().unwrap_err();
+    assert_eq!(err.message, "unclosed list: missing ')'");
+    assert_eq!((err.span.line, err.span.column), (2, 1));
+}
+
+#[test]
+fn test_reader_macros_from_the_given_table() {
+    let mut table = Readtable::default();
+    table
+        .set("#even", |datum| match datum {
+            Expr::Number(n) => Ok(Expr::Bool(n % 2 == 0)),
+            other => Err(format!("#even expects a number, got {}", other)),
+        })
+        .unwrap();
+    let source = "(list #even ; four\n 4)";
+    let tree = parse_cst_with(source, &table);
+    assert_eq!(tree.to_string(), source);
+    let SyntaxNode::List { items, .. } = &tree.forms[0] else {
+        panic!("expected a list");
+    };
+    assert!(matches!(&items[1], SyntaxNode::Quote { quote, .. } if quote.text == "#even"));
+    assert_eq!(
+        tree.lower_with(&table).unwrap().forms,
+        vec![Expr::List(vec![
+            Expr::Symbol("list".into()),
+            Expr::Bool(true)
+        ])]
+    );
+    assert_eq!(items[1].lower_with(&table), Ok(Expr::Bool(true)));
+}
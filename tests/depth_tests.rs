@@ -0,0 +1,123 @@
+use lisp_interpreter_rs::*;
+
+// `depth` lists, one inside the next, around an atom
+fn nested(depth: usize) -> String {
+    format!("{}x{}", "(".repeat(depth), ")".repeat(depth))
+}
+
+#[test]
+fn test_pathological_nesting_is_an_error() {
+    let source = "(".repeat(300_000);
+    let err = parse_program(&source).unwrap_err();
+    assert_eq!(
+        err.message,
+        format!("nesting too deep: more than {} levels", DEFAULT_MAX_DEPTH)
+    );
+    assert_eq!(err.span.start, DEFAULT_MAX_DEPTH);
+
+    let err = parse_program(&nested(300_000)).unwrap_err();
+    assert!(err.message.starts_with("nesting too deep"));
+}
+
+#[test]
+fn test_nesting_up_to_the_limit_is_read() {
+    let program = parse_program(&nested(DEFAULT_MAX_DEPTH)).unwrap();
+    assert_eq!(program.forms.len(), 1);
+    assert!(parse_program(&nested(DEFAULT_MAX_DEPTH + 1)).is_err());
+}
+
+#[test]
+fn test_deepest_readable_forms_evaluate() {
+    // Test threads have the 2 MiB stack of any spawned thread
+    let depth = DEFAULT_MAX_DEPTH - 1;
+    let mut env = default_env();
+    let sum = format!("{}0{}", "(+ 1 ".repeat(depth), ")".repeat(depth));
+    let form = parse_program(&sum).unwrap().forms.remove(0);
+    assert_eq!(eval(&form, &mut env), Ok(Expr::Number(depth as i64)));
+
+    let branches = format!("{}0{}", "(if #t ".repeat(depth), " 1)".repeat(depth));
+    let form = parse_program(&branches).unwrap().forms.remove(0);
+    assert_eq!(eval(&form, &mut env), Ok(Expr::Number(0)));
+
+    let quoted = format!("(quote {})", nested(depth - 1));
+    let form = parse_program(&quoted).unwrap().forms.remove(0);
+    let value = eval(&form, &mut env).unwrap();
+    assert_eq!(value.to_string(), nested(depth - 1));
+    assert_eq!(value.clone(), value);
+}
+
+#[test]
+fn test_configurable_limit() {
+    let mut forms = Parser::new(Lexer::new("((a)) (((b)))")).with_max_depth(2);
+    assert!(forms.next().unwrap().is_ok());
+    let err = forms.next().unwrap().unwrap_err();
+    assert_eq!(err.message, "nesting too deep: more than 2 levels");
+    assert_eq!(err.span.start, 8);
+
+    // Quotes and vectors nest too
+    let mut forms = Parser::new(Lexer::new("'''x")).with_max_depth(2);
+    assert!(forms.next().unwrap().is_err());
+    let mut forms = Parser::new(Lexer::new("#(#(#(1)))")).with_max_depth(2);
+    assert!(forms.next().unwrap().is_err());
+
+    let mut reader = IncrementalParser::new();
+    reader.set_max_depth(1);
+    reader.push("(a) ((b))");
+    reader.finish();
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_err());
+}
+
+#[test]
+fn test_stray_close_paren_is_rejected() {
+    assert_eq!(
+        parse(&[")"]).unwrap_err(),
+        "unexpected ')' with no list to close"
+    );
+    let err = parse_program("(a) ) b").unwrap_err();
+    assert_eq!(err.message, "unexpected ')' with no list to close");
+    assert_eq!((err.span.start, err.span.column), (4, 5));
+    let tokens = tokenize_spanned(")");
+    assert!(parse_spanned(&tokens).is_err());
+}
+
+#[test]
+fn test_quote_before_close_paren() {
+    let err = parse_program("(a ')").unwrap_err();
+    assert_eq!(err.message, "missing datum after '");
+    assert_eq!(err.span.start, 3);
+}
+
+#[test]
+fn test_recovering_parser_survives_deep_input() {
+    let source = format!("{}\n(ok)", nested(300_000));
+    let (program, diagnostics) = parse_recovering(&source);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.starts_with("nesting too deep"));
+    // The form after the deep one is still read
+    assert_eq!(
+        program.forms.last(),
+        Some(&Expr::List(vec![Expr::Symbol("ok".into())]))
+    );
+}
+
+#[test]
+fn test_syntax_tree_of_deep_input() {
+    let source = nested(300_000);
+    let tree = parse_cst(&source);
+    assert_eq!(tree.to_string(), source);
+    assert!(
+        tree.lower()
+            .unwrap_err()
+            .message
+            .starts_with("nesting too deep")
+    );
+}
+
+#[test]
+fn test_long_comment_and_prefix_chains() {
+    let source = format!("{}1 2", "#;".repeat(200_000));
+    assert_eq!(parse_program(&source).unwrap().forms, vec![]);
+    let source = format!("#;{}x 5", "'".repeat(200_000));
+    assert_eq!(parse_program(&source).unwrap().forms, vec![Expr::Number(5)]);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_parse_hash_boolean_literals() {
+    assert_eq!(parse(&["#t"]).unwrap(), Expr::Bool(true));
+    assert_eq!(parse(&["#f"]).unwrap(), Expr::Bool(false));
+    assert_eq!(parse(&["#true"]).unwrap(), Expr::Bool(true));
+    assert_eq!(parse(&["#false"]).unwrap(), Expr::Bool(false));
+    // The original spellings are still accepted
+    assert_eq!(parse(&["true"]).unwrap(), Expr::Bool(true));
+    assert_eq!(parse(&["false"]).unwrap(), Expr::Bool(false));
+}
+
+#[test]
+fn test_hash_booleans_in_expressions() {
+    let mut env = default_env();
+    assert_eq!(run("(if #t 1 2)", &mut env).unwrap(), Expr::Number(1));
+    assert_eq!(run("(if #false 1 2)", &mut env).unwrap(), Expr::Number(2));
+    assert_eq!(
+        run("(boolean=? #t true)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_default_dialect_is_c_like() {
+    assert_eq!(default_env().dialect(), Dialect::CLike);
+}
+
+#[test]
+fn test_c_like_truthiness() {
+    let mut env = Env::with_dialect(Dialect::CLike);
+    assert_eq!(run("(if 0 1 2)", &mut env).unwrap(), Expr::Number(2));
+    assert_eq!(run("(if -3 1 2)", &mut env).unwrap(), Expr::Number(1));
+    assert_eq!(
+        run("(if \"text\" 1 2)", &mut env).unwrap_err(),
+        "if condition must be a boolean or a number"
+    );
+}
+
+#[test]
+fn test_scheme_truthiness() {
+    let mut env = Env::with_dialect(Dialect::Scheme);
+    assert_eq!(run("(if 0 1 2)", &mut env).unwrap(), Expr::Number(1));
+    assert_eq!(run("(if \"\" 1 2)", &mut env).unwrap(), Expr::Number(1));
+    assert_eq!(
+        run("(if (quote ()) 1 2)", &mut env).unwrap(),
+        Expr::Number(1)
+    );
+    assert_eq!(run("(if #f 1 2)", &mut env).unwrap(), Expr::Number(2));
+}
+
+#[test]
+fn test_dialect_applies_inside_functions() {
+    let mut env = Env::with_dialect(Dialect::Scheme);
+    run(
+        "(define pick (lambda (c) (if c (quote yes) (quote no))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(pick 0)", &mut env).unwrap(),
+        Expr::Symbol("yes".into())
+    );
+
+    env.set_dialect(Dialect::CLike);
+    assert_eq!(
+        run("(pick 0)", &mut env).unwrap(),
+        Expr::Symbol("no".into())
+    );
+}
+
+#[test]
+fn test_is_truthy() {
+    assert_eq!(Dialect::Scheme.is_truthy(&Expr::Number(0)), Ok(true));
+    assert_eq!(Dialect::CLike.is_truthy(&Expr::Number(0)), Ok(false));
+    assert_eq!(Dialect::Scheme.is_truthy(&Expr::Bool(false)), Ok(false));
+    assert!(Dialect::CLike.is_truthy(&Expr::Str("x".into())).is_err());
+}
+
+#[test]
+fn test_booleans_print_as_hash_literals() {
+    assert_eq!(Expr::Bool(true).to_string(), "#t");
+    assert_eq!(Expr::Bool(false).written().to_string(), "#f");
+}
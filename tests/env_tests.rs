@@ -6,14 +6,14 @@ fn test_define_and_lookup() {
 
     // Define a variable
     let define_expr = Expr::List(vec![
-        Expr::Symbol("define".to_string()),
-        Expr::Symbol("x".to_string()),
+        Expr::Symbol("define".into()),
+        Expr::Symbol("x".into()),
         Expr::Number(42),
     ]);
     eval(&define_expr, &mut env).unwrap();
 
     // Lookup the variable
-    let lookup_expr = Expr::Symbol("x".to_string());
+    let lookup_expr = Expr::Symbol("x".into());
     let result = eval(&lookup_expr, &mut env).unwrap();
     assert_eq!(result, Expr::Number(42));
 }
@@ -24,16 +24,16 @@ fn test_use_defined_variable() {
 
     // Define a variable
     let define_expr = Expr::List(vec![
-        Expr::Symbol("define".to_string()),
-        Expr::Symbol("x".to_string()),
+        Expr::Symbol("define".into()),
+        Expr::Symbol("x".into()),
         Expr::Number(10),
     ]);
     eval(&define_expr, &mut env).unwrap();
 
     // Use it in an expression
     let expr = Expr::List(vec![
-        Expr::Symbol("+".to_string()),
-        Expr::Symbol("x".to_string()),
+        Expr::Symbol("+".into()),
+        Expr::Symbol("x".into()),
         Expr::Number(5),
     ]);
     let result = eval(&expr, &mut env).unwrap();
@@ -42,7 +42,7 @@ fn test_use_defined_variable() {
 
 #[test]
 fn test_undefined_variable_error() {
-    let expr = Expr::Symbol("undefined_var".to_string());
+    let expr = Expr::Symbol("undefined_var".into());
     let result = eval(&expr, &mut default_env());
     assert!(result.is_err());
 }
@@ -53,22 +53,22 @@ fn test_redefine_variable() {
 
     // Define x = 10
     let define_expr = Expr::List(vec![
-        Expr::Symbol("define".to_string()),
-        Expr::Symbol("x".to_string()),
+        Expr::Symbol("define".into()),
+        Expr::Symbol("x".into()),
         Expr::Number(10),
     ]);
     eval(&define_expr, &mut env).unwrap();
 
     // Redefine x = 20
     let redefine_expr = Expr::List(vec![
-        Expr::Symbol("define".to_string()),
-        Expr::Symbol("x".to_string()),
+        Expr::Symbol("define".into()),
+        Expr::Symbol("x".into()),
         Expr::Number(20),
     ]);
     eval(&redefine_expr, &mut env).unwrap();
 
     // Lookup should return new value
-    let lookup_expr = Expr::Symbol("x".to_string());
+    let lookup_expr = Expr::Symbol("x".into());
     let result = eval(&lookup_expr, &mut env).unwrap();
     assert_eq!(result, Expr::Number(20));
 }
@@ -80,8 +80,8 @@ fn test_multiple_variables() {
     // Define multiple variables
     eval(
         &Expr::List(vec![
-            Expr::Symbol("define".to_string()),
-            Expr::Symbol("a".to_string()),
+            Expr::Symbol("define".into()),
+            Expr::Symbol("a".into()),
             Expr::Number(5),
         ]),
         &mut env,
@@ -90,8 +90,8 @@ fn test_multiple_variables() {
 
     eval(
         &Expr::List(vec![
-            Expr::Symbol("define".to_string()),
-            Expr::Symbol("b".to_string()),
+            Expr::Symbol("define".into()),
+            Expr::Symbol("b".into()),
             Expr::Number(10),
         ]),
         &mut env,
@@ -100,8 +100,8 @@ fn test_multiple_variables() {
 
     eval(
         &Expr::List(vec![
-            Expr::Symbol("define".to_string()),
-            Expr::Symbol("c".to_string()),
+            Expr::Symbol("define".into()),
+            Expr::Symbol("c".into()),
             Expr::Number(15),
         ]),
         &mut env,
@@ -110,10 +110,10 @@ fn test_multiple_variables() {
 
     // Use all variables
     let expr = Expr::List(vec![
-        Expr::Symbol("+".to_string()),
-        Expr::Symbol("a".to_string()),
-        Expr::Symbol("b".to_string()),
-        Expr::Symbol("c".to_string()),
+        Expr::Symbol("+".into()),
+        Expr::Symbol("a".into()),
+        Expr::Symbol("b".into()),
+        Expr::Symbol("c".into()),
     ]);
     let result = eval(&expr, &mut env).unwrap();
     assert_eq!(result, Expr::Number(30));
@@ -125,10 +125,10 @@ fn test_define_with_expression() {
 
     // Define x = (+ 2 3)
     let define_expr = Expr::List(vec![
-        Expr::Symbol("define".to_string()),
-        Expr::Symbol("x".to_string()),
+        Expr::Symbol("define".into()),
+        Expr::Symbol("x".into()),
         Expr::List(vec![
-            Expr::Symbol("+".to_string()),
+            Expr::Symbol("+".into()),
             Expr::Number(2),
             Expr::Number(3),
         ]),
@@ -136,7 +136,7 @@ fn test_define_with_expression() {
     eval(&define_expr, &mut env).unwrap();
 
     // Lookup x
-    let lookup_expr = Expr::Symbol("x".to_string());
+    let lookup_expr = Expr::Symbol("x".into());
     let result = eval(&lookup_expr, &mut env).unwrap();
     assert_eq!(result, Expr::Number(5));
 }
@@ -148,8 +148,8 @@ fn test_variable_in_nested_expression() {
     // Define x = 3
     eval(
         &Expr::List(vec![
-            Expr::Symbol("define".to_string()),
-            Expr::Symbol("x".to_string()),
+            Expr::Symbol("define".into()),
+            Expr::Symbol("x".into()),
             Expr::Number(3),
         ]),
         &mut env,
@@ -158,10 +158,10 @@ fn test_variable_in_nested_expression() {
 
     // (+ (* x 4) 5) = (+ (* 3 4) 5) = (+ 12 5) = 17
     let expr = Expr::List(vec![
-        Expr::Symbol("+".to_string()),
+        Expr::Symbol("+".into()),
         Expr::List(vec![
-            Expr::Symbol("*".to_string()),
-            Expr::Symbol("x".to_string()),
+            Expr::Symbol("*".into()),
+            Expr::Symbol("x".into()),
             Expr::Number(4),
         ]),
         Expr::Number(5),
@@ -177,8 +177,8 @@ fn test_define_using_previous_variable() {
     // Define x = 10
     eval(
         &Expr::List(vec![
-            Expr::Symbol("define".to_string()),
-            Expr::Symbol("x".to_string()),
+            Expr::Symbol("define".into()),
+            Expr::Symbol("x".into()),
             Expr::Number(10),
         ]),
         &mut env,
@@ -188,11 +188,11 @@ fn test_define_using_previous_variable() {
     // Define y = (+ x 5) = 15
     eval(
         &Expr::List(vec![
-            Expr::Symbol("define".to_string()),
-            Expr::Symbol("y".to_string()),
+            Expr::Symbol("define".into()),
+            Expr::Symbol("y".into()),
             Expr::List(vec![
-                Expr::Symbol("+".to_string()),
-                Expr::Symbol("x".to_string()),
+                Expr::Symbol("+".into()),
+                Expr::Symbol("x".into()),
                 Expr::Number(5),
             ]),
         ]),
@@ -202,9 +202,9 @@ fn test_define_using_previous_variable() {
 
     // Use both
     let expr = Expr::List(vec![
-        Expr::Symbol("*".to_string()),
-        Expr::Symbol("x".to_string()),
-        Expr::Symbol("y".to_string()),
+        Expr::Symbol("*".into()),
+        Expr::Symbol("x".into()),
+        Expr::Symbol("y".into()),
     ]);
     let result = eval(&expr, &mut env).unwrap();
     assert_eq!(result, Expr::Number(150)); // 10 * 15
@@ -217,15 +217,15 @@ fn test_define_negative_number() {
     // Define x = -42
     eval(
         &Expr::List(vec![
-            Expr::Symbol("define".to_string()),
-            Expr::Symbol("x".to_string()),
+            Expr::Symbol("define".into()),
+            Expr::Symbol("x".into()),
             Expr::Number(-42),
         ]),
         &mut env,
     )
     .unwrap();
 
-    let lookup_expr = Expr::Symbol("x".to_string());
+    let lookup_expr = Expr::Symbol("x".into());
     let result = eval(&lookup_expr, &mut env).unwrap();
     assert_eq!(result, Expr::Number(-42));
 }
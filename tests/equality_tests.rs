@@ -0,0 +1,155 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_eq_numbers_and_booleans() {
+    let mut env = default_env();
+    assert_eq!(run("(eq? 3 3)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(eqv? 3 4)", &mut env).unwrap(), Expr::Bool(false));
+    assert_eq!(run("(eq? true true)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(eqv? 1 true)", &mut env).unwrap(), Expr::Bool(false));
+}
+
+#[test]
+fn test_eq_symbols() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(eq? (quote abc) (quote abc))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(eq? (quote abc) (quote abd))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_eq_empty_lists() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(eq? (quote ()) (quote ()))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_equal_nested_lists() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(equal? (quote (1 (2 3) x)) (quote (1 (2 3) x)))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(equal? (quote (1 (2 3))) (quote (1 (2 4))))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(equal? (quote (1 2)) (quote (1 2 3)))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_lambda_identity() {
+    // The same procedure is eq? to itself, but two evaluations of an
+    // identical lambda form produce distinct procedures
+    let mut env = default_env();
+    run("(define f (lambda (x) x))", &mut env).unwrap();
+    run("(define g f)", &mut env).unwrap();
+    run("(define h (lambda (x) x))", &mut env).unwrap();
+    assert_eq!(run("(eq? f g)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(eqv? f h)", &mut env).unwrap(), Expr::Bool(false));
+    assert_eq!(run("(equal? f h)", &mut env).unwrap(), Expr::Bool(false));
+}
+
+#[test]
+fn test_equality_arity_error() {
+    let mut env = default_env();
+    let result = run("(equal? 1)", &mut env);
+    assert_eq!(result.unwrap_err(), "equal? requires exactly 2 arguments");
+}
+
+#[test]
+fn test_not() {
+    let mut env = default_env();
+    assert_eq!(run("(not false)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(not true)", &mut env).unwrap(), Expr::Bool(false));
+}
+
+#[test]
+fn test_not_in_scheme_dialect() {
+    // Scheme: only false is negated to true; every other value counts as true
+    let mut env = Env::with_dialect(Dialect::Scheme);
+    assert_eq!(run("(not 0)", &mut env).unwrap(), Expr::Bool(false));
+    assert_eq!(
+        run("(not (quote ()))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_not_in_c_like_dialect() {
+    // C-like: 0 is false, and non-numeric values are rejected
+    let mut env = Env::with_dialect(Dialect::CLike);
+    assert_eq!(run("(not 0)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(not 5)", &mut env).unwrap(), Expr::Bool(false));
+    assert!(run("(not (quote ()))", &mut env).is_err());
+}
+
+#[test]
+fn test_boolean_equal() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(boolean=? true true true)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(boolean=? false true)", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_boolean_equal_errors() {
+    let mut env = default_env();
+    assert!(run("(boolean=? true 1)", &mut env).is_err());
+    assert!(run("(boolean=? true)", &mut env).is_err());
+}
+
+#[test]
+fn test_quote_returns_data() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(quote (+ 1 2))", &mut env).unwrap(),
+        Expr::List(vec![
+            Expr::Symbol("+".into()),
+            Expr::Number(1),
+            Expr::Number(2)
+        ])
+    );
+}
+
+#[test]
+fn test_eqv_lists_compare_elements_with_eqv() {
+    // Lists have no identity, so eqv? compares them element by element, but
+    // with eqv? rather than equal?: separately built strings inside differ
+    let mut env = default_env();
+    assert_eq!(
+        run("(eqv? (quote (1 a)) (quote (1 a)))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(eqv? (quote (1 a)) (quote (1 b)))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(eqv? (quote (\"s\")) (quote (\"s\")))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(equal? (quote (\"s\")) (quote (\"s\")))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
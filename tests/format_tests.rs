@@ -0,0 +1,111 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+fn format_str(input: &str) -> String {
+    match run(input, &mut default_env()).unwrap() {
+        Expr::Str(s) => s.to_string(),
+        other => panic!("expected a string, got {}", other),
+    }
+}
+
+#[test]
+fn test_display_and_write() {
+    assert_eq!(
+        format_str(r#"(format "~a and ~s" "text" "text")"#),
+        r#"text and "text""#
+    );
+    assert_eq!(format_str(r#"(format "~a ~s" #\x #\x)"#), r#"x #\x"#);
+    assert_eq!(
+        format_str(r#"(format "list: ~a" (quote (1 "two" 3)))"#),
+        "list: (1 two 3)"
+    );
+}
+
+#[test]
+fn test_number_directives() {
+    assert_eq!(
+        format_str(r#"(format "~d ~x ~o ~b" 255 255 255 5)"#),
+        "255 ff 377 101"
+    );
+    assert_eq!(format_str(r#"(format "~x" -26)"#), "-1a");
+    assert_eq!(format_str(r#"(format "~d" 2.5)"#), "2.5");
+}
+
+#[test]
+fn test_number_directive_type_errors() {
+    assert!(run(r#"(format "~x" 2.5)"#, &mut default_env()).is_err());
+    assert!(run(r#"(format "~d" "7")"#, &mut default_env()).is_err());
+}
+
+#[test]
+fn test_newline_and_tilde() {
+    assert_eq!(format_str(r#"(format "a~%b~~")"#), "a\nb~");
+}
+
+#[test]
+fn test_width() {
+    assert_eq!(format_str(r#"(format "[~5d]" 42)"#), "[   42]");
+    assert_eq!(format_str(r#"(format "[~6a]" "ab")"#), "[ab    ]");
+    assert_eq!(format_str(r#"(format "[~6@a]" "ab")"#), "[    ab]");
+}
+
+#[test]
+fn test_padding_character() {
+    assert_eq!(format_str(r#"(format "[~5,'0d]" -42)"#), "[-0042]");
+    assert_eq!(format_str(r#"(format "[~4,'.@d]" 7)"#), "[7...]");
+    assert_eq!(format_str(r#"(format "[~8,'0b]" 5)"#), "[00000101]");
+}
+
+#[test]
+fn test_width_never_truncates() {
+    // Text longer than the width is never truncated
+    assert_eq!(format_str(r#"(format "[~2d]" 12345)"#), "[12345]");
+}
+
+#[test]
+fn test_width_limit() {
+    let mut env = default_env();
+    assert_eq!(
+        run(r#"(format "~5000000000a" 1)"#, &mut env).unwrap_err(),
+        "format: width is more than the maximum of 10000"
+    );
+    assert!(run(r#"(format "~99999999999999999999999a" 1)"#, &mut env).is_err());
+}
+
+#[test]
+fn test_destination() {
+    assert_eq!(format_str(r#"(format #f "~a!" 1)"#), "1!");
+    assert_eq!(
+        run(r#"(format #t "")"#, &mut default_env()).unwrap(),
+        Expr::Unspecified
+    );
+}
+
+#[test]
+fn test_argument_errors() {
+    let mut env = default_env();
+    assert_eq!(
+        run(r#"(format "~a ~a" 1)"#, &mut env).unwrap_err(),
+        "format: not enough arguments for ~a"
+    );
+    assert!(run(r#"(format "~a" 1 2)"#, &mut env).is_err());
+    assert!(run(r#"(format "~q" 1)"#, &mut env).is_err());
+    assert!(run(r#"(format "oops ~")"#, &mut env).is_err());
+    assert!(run("(format 5)", &mut env).is_err());
+}
+
+#[test]
+fn test_display_and_write_procedures() {
+    let mut env = default_env();
+    assert_eq!(
+        run(r#"(display "text")"#, &mut env).unwrap(),
+        Expr::Unspecified
+    );
+    assert_eq!(run(r#"(write #\x)"#, &mut env).unwrap(), Expr::Unspecified);
+    assert_eq!(
+        run("(display 1 2)", &mut env).unwrap_err(),
+        "display requires exactly 1 argument"
+    );
+}
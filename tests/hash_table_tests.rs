@@ -0,0 +1,232 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_set_and_ref() {
+    let mut env = default_env();
+    run("(define t (make-hash-table))", &mut env).unwrap();
+    run("(hash-table-set! t (quote apple) 3)", &mut env).unwrap();
+    run("(hash-table-set! t \"pear\" 5)", &mut env).unwrap();
+    assert_eq!(
+        run("(hash-table-ref t (quote apple))", &mut env).unwrap(),
+        Expr::Number(3)
+    );
+    // The default table uses equal?, so a fresh string finds the entry
+    assert_eq!(
+        run("(hash-table-ref t (string-append \"pe\" \"ar\"))", &mut env).unwrap(),
+        Expr::Number(5)
+    );
+    assert_eq!(
+        run("(hash-table-count t)", &mut env).unwrap(),
+        Expr::Number(2)
+    );
+}
+
+#[test]
+fn test_ref_with_default_and_missing_key() {
+    let mut env = default_env();
+    run("(define t (make-hash-table))", &mut env).unwrap();
+    assert_eq!(
+        run("(hash-table-ref t 1 0)", &mut env).unwrap(),
+        Expr::Number(0)
+    );
+    assert_eq!(
+        run("(hash-table-ref/default t 1 42)", &mut env).unwrap(),
+        Expr::Number(42)
+    );
+    assert_eq!(
+        run("(hash-table-ref t 1)", &mut env).unwrap_err(),
+        "hash-table-ref: key not found: 1"
+    );
+}
+
+#[test]
+fn test_structural_keys() {
+    let mut env = default_env();
+    run("(define t (make-hash-table equal?))", &mut env).unwrap();
+    run("(hash-table-set! t (quote (1 2)) (quote list))", &mut env).unwrap();
+    run("(hash-table-set! t (vector 1 2) (quote vector))", &mut env).unwrap();
+    assert_eq!(
+        run("(hash-table-ref t (quote (1 2)))", &mut env).unwrap(),
+        Expr::Symbol("list".into())
+    );
+    assert_eq!(
+        run("(hash-table-ref t #(1 2))", &mut env).unwrap(),
+        Expr::Symbol("vector".into())
+    );
+}
+
+#[test]
+fn test_identity_keys() {
+    let mut env = default_env();
+    run("(define t (make-hash-table eq?))", &mut env).unwrap();
+    run("(define key (vector 1))", &mut env).unwrap();
+    run("(hash-table-set! t key 1)", &mut env).unwrap();
+    run("(hash-table-set! t 7 2)", &mut env).unwrap();
+    assert_eq!(
+        run("(hash-table-contains? t key)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(hash-table-contains? t (vector 1))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(hash-table-contains? t 7)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_unsupported_equivalence() {
+    assert!(run("(make-hash-table +)", &mut default_env()).is_err());
+}
+
+#[test]
+fn test_delete() {
+    let mut env = default_env();
+    run("(define t (make-hash-table))", &mut env).unwrap();
+    run("(hash-table-set! t 1 (quote one))", &mut env).unwrap();
+    run("(hash-table-set! t 2 (quote two))", &mut env).unwrap();
+    run("(hash-table-set! t 3 (quote three))", &mut env).unwrap();
+    run("(hash-table-delete! t 1)", &mut env).unwrap();
+    // Deleting a missing key is not an error
+    run("(hash-table-delete! t 1)", &mut env).unwrap();
+    assert_eq!(
+        run("(hash-table-contains? t 1)", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    // The remaining entries are still reachable after being moved
+    assert_eq!(
+        run("(hash-table-ref t 3)", &mut env).unwrap(),
+        Expr::Symbol("three".into())
+    );
+    assert_eq!(
+        run("(hash-table-ref t 2)", &mut env).unwrap(),
+        Expr::Symbol("two".into())
+    );
+}
+
+#[test]
+fn test_keys_values_and_alist() {
+    let mut env = default_env();
+    run(
+        "(define t (alist->hash-table (quote ((a 1) (b 2)))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(hash-table-keys t)", &mut env).unwrap().to_string(),
+        "(a b)"
+    );
+    assert_eq!(
+        run("(hash-table-values t)", &mut env).unwrap().to_string(),
+        "(1 2)"
+    );
+    assert_eq!(
+        run("(hash-table->alist t)", &mut env).unwrap().to_string(),
+        "((a 1) (b 2))"
+    );
+}
+
+#[test]
+fn test_alist_first_entry_wins() {
+    let mut env = default_env();
+    run(
+        "(define t (alist->hash-table (quote ((a 1) (b 2) (a 3)))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(hash-table-ref t (quote a))", &mut env).unwrap(),
+        Expr::Number(1)
+    );
+    assert_eq!(
+        run("(hash-table-count t)", &mut env).unwrap(),
+        Expr::Number(2)
+    );
+}
+
+#[test]
+fn test_vector_containing_itself_as_equal_key() {
+    let mut env = default_env();
+    run("(define v (make-vector 1 0))", &mut env).unwrap();
+    run("(vector-set! v 0 v)", &mut env).unwrap();
+    run("(define w (make-vector 1 0))", &mut env).unwrap();
+    run("(vector-set! w 0 (vector w))", &mut env).unwrap();
+    run("(define t (make-hash-table))", &mut env).unwrap();
+    run("(hash-table-set! t v 1)", &mut env).unwrap();
+    // w unfolds to the same infinite vector as v
+    assert_eq!(
+        run("(hash-table-ref/default t w 0)", &mut env).unwrap(),
+        Expr::Number(1)
+    );
+}
+
+#[test]
+fn test_update() {
+    let mut env = default_env();
+    run("(define counts (make-hash-table))", &mut env).unwrap();
+    run(
+        "(hash-table-update! counts (quote x) (lambda (n) (+ n 1)) 0)",
+        &mut env,
+    )
+    .unwrap();
+    run(
+        "(hash-table-update! counts (quote x) (lambda (n) (+ n 1)) 0)",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(hash-table-ref counts (quote x))", &mut env).unwrap(),
+        Expr::Number(2)
+    );
+}
+
+#[test]
+fn test_update_missing_key_without_default() {
+    let mut env = default_env();
+    run("(define counts (make-hash-table))", &mut env).unwrap();
+    let result = run(
+        "(hash-table-update! counts (quote y) (lambda (n) n))",
+        &mut env,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_walk() {
+    let mut env = default_env();
+    run(
+        "(define t (alist->hash-table (quote ((1 10) (2 20)))))",
+        &mut env,
+    )
+    .unwrap();
+    run("(define total (make-vector 1 0))", &mut env).unwrap();
+    run(
+        "(hash-table-walk t (lambda (k v) (vector-set! total 0 (+ (vector-ref total 0) k v))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(vector-ref total 0)", &mut env).unwrap(),
+        Expr::Number(33)
+    );
+}
+
+#[test]
+fn test_rust_api() {
+    let mut table = HashTable::new(KeyEquality::Equal);
+    assert!(table.is_empty());
+    table.insert(Expr::Str("k".into()), Expr::Number(1));
+    assert_eq!(
+        table.insert(Expr::Str("k".into()), Expr::Number(2)),
+        Some(Expr::Number(1))
+    );
+    assert_eq!(table.get(&Expr::Str("k".into())), Some(&Expr::Number(2)));
+    assert_eq!(table.len(), 1);
+    assert_eq!(table.remove(&Expr::Str("k".into())), Some(Expr::Number(2)));
+    assert!(table.is_empty());
+}
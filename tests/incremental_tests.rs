@@ -0,0 +1,193 @@
+use lisp_interpreter_rs::*;
+
+// Every form available so far, panicking on parse errors
+fn forms(reader: &mut IncrementalParser) -> Vec<Expr> {
+    reader.map(|form| form.unwrap().0).collect()
+}
+
+#[test]
+fn test_forms_arrive_as_they_complete() {
+    let mut reader = IncrementalParser::new();
+    reader.push("(define x 1) (define");
+    assert_eq!(forms(&mut reader).len(), 1);
+    assert!(reader.is_incomplete());
+    assert_eq!(reader.depth(), 1);
+    reader.push(" y 2)\n");
+    assert_eq!(forms(&mut reader).len(), 1);
+    assert!(!reader.is_incomplete());
+    assert_eq!(reader.depth(), 0);
+}
+
+#[test]
+fn test_depth_of_nested_lists() {
+    let mut reader = IncrementalParser::new();
+    reader.push("(a (b #(c");
+    assert!(forms(&mut reader).is_empty());
+    assert_eq!(reader.depth(), 3);
+    reader.push(")");
+    assert!(forms(&mut reader).is_empty());
+    assert_eq!(reader.depth(), 2);
+    reader.push("))");
+    assert_eq!(forms(&mut reader).len(), 1);
+    assert_eq!(reader.depth(), 0);
+}
+
+#[test]
+fn test_open_string_is_incomplete() {
+    let mut reader = IncrementalParser::new();
+    reader.push("(display \"a (b");
+    assert!(forms(&mut reader).is_empty());
+    assert!(reader.is_incomplete());
+    assert_eq!(reader.depth(), 1);
+    reader.push(" c\")");
+    assert_eq!(
+        forms(&mut reader),
+        vec![Expr::List(vec![
+            Expr::Symbol("display".into()),
+            Expr::Str("a (b c".into())
+        ])]
+    );
+}
+
+#[test]
+fn test_chunks_can_split_tokens() {
+    let mut reader = IncrementalParser::new();
+    reader.push("12");
+    assert!(forms(&mut reader).is_empty());
+    reader.push("3 ");
+    assert_eq!(forms(&mut reader), vec![Expr::Number(123)]);
+}
+
+#[test]
+fn test_quote_and_comments_wait_for_more() {
+    let mut reader = IncrementalParser::new();
+    reader.push("'");
+    assert!(forms(&mut reader).is_empty());
+    assert!(reader.is_incomplete());
+    reader.push("x #| still");
+    assert_eq!(forms(&mut reader).len(), 1);
+    assert!(reader.is_incomplete());
+    reader.push(" a comment |# ; line\n");
+    assert!(forms(&mut reader).is_empty());
+    assert!(!reader.is_incomplete());
+}
+
+#[test]
+fn test_malformed_input_is_an_error() {
+    let mut reader = IncrementalParser::new();
+    reader.push(") (+ 1 \"\\q\") 5 ");
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.message, "unexpected ')' with no list to close");
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.message, "unknown escape in string: \\q");
+    // The reader carries on after an error
+    assert_eq!(forms(&mut reader), vec![Expr::Number(5)]);
+}
+
+#[test]
+fn test_finish_reports_unfinished_forms() {
+    let mut reader = IncrementalParser::new();
+    reader.push("42");
+    assert!(forms(&mut reader).is_empty());
+    reader.push("\n(+ 1");
+    reader.finish();
+    assert_eq!(reader.next().unwrap().unwrap().0, Expr::Number(42));
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.message, "unclosed list: missing ')'");
+    assert_eq!((err.span.line, err.span.column), (2, 1));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_spans_continue_across_chunks() {
+    let mut reader = IncrementalParser::new();
+    reader.push("(a)\n  (b");
+    reader.next().unwrap().unwrap();
+    assert!(reader.next().is_none());
+    reader.push("\n c)");
+    let (_, span) = reader.next().unwrap().unwrap();
+    assert_eq!(
+        (span.line, span.column, span.start, span.end),
+        (2, 3, 6, 12)
+    );
+    assert_eq!(reader.pending(), "");
+}
+
+#[test]
+fn test_eval_forms_from_chunks() {
+    let mut reader = IncrementalParser::new();
+    let mut env = Env::new();
+    let mut results = Vec::new();
+    for chunk in ["(define sq (lambda", " (x) (* x x)))\n(sq", " 9)\n"] {
+        reader.push(chunk);
+        for form in &mut reader {
+            results.push(eval(&form.unwrap().0, &mut env).unwrap());
+        }
+    }
+    env.set_source_map(reader.take_source_map());
+    assert_eq!(results, vec![Expr::Unspecified, Expr::Number(81)]);
+}
+
+#[test]
+fn test_datum_comment_waits_for_its_datum() {
+    let mut reader = IncrementalParser::new();
+    reader.push("#;");
+    assert!(forms(&mut reader).is_empty());
+    assert!(reader.is_incomplete());
+    reader.push("(a");
+    assert!(forms(&mut reader).is_empty());
+    assert!(reader.is_incomplete());
+    assert_eq!(reader.depth(), 1);
+    reader.push(" b) 5 ");
+    assert_eq!(forms(&mut reader), vec![Expr::Number(5)]);
+    assert!(!reader.is_incomplete());
+}
+
+#[test]
+fn test_datum_comment_inside_an_open_list() {
+    let mut reader = IncrementalParser::new();
+    reader.push("(x #;(y");
+    assert!(forms(&mut reader).is_empty());
+    assert_eq!(reader.depth(), 2);
+    reader.push(") z)");
+    assert_eq!(
+        forms(&mut reader),
+        vec![Expr::List(vec![
+            Expr::Symbol("x".into()),
+            Expr::Symbol("z".into())
+        ])]
+    );
+}
+
+#[test]
+fn test_datum_comment_at_the_end_of_input() {
+    let mut reader = IncrementalParser::new();
+    reader.push("1 #;");
+    reader.finish();
+    assert_eq!(reader.next().unwrap().unwrap().0, Expr::Number(1));
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.message, "missing datum after #;");
+    assert_eq!((err.span.start, err.span.column), (2, 3));
+    assert!(reader.next().is_none());
+    assert!(!reader.is_incomplete());
+}
+
+#[test]
+fn test_read_text_is_dropped() {
+    let mut reader = IncrementalParser::new();
+    reader.push("(first form)\n");
+    reader.next().unwrap().unwrap();
+    reader.push("(b (c))");
+    let (form, span) = reader.next().unwrap().unwrap();
+    assert!(!format!("{:?}", reader).contains("first form"));
+    // Spans still count from the start of all the input
+    assert_eq!((span.start, span.end, span.line), (13, 20, 2));
+    let Expr::List(items) = &form else {
+        panic!("expected a list");
+    };
+    let Expr::List(inner) = &items[1] else {
+        panic!("expected a list");
+    };
+    let source_map = reader.take_source_map();
+    assert_eq!(source_map.span_of(inner).map(|span| span.start), Some(16));
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_keywords_self_evaluate() {
+    let mut env = default_env();
+    assert_eq!(
+        run(":name", &mut env).unwrap(),
+        Expr::Keyword("name".into())
+    );
+    assert_eq!(run(":name", &mut env).unwrap().to_string(), ":name");
+}
+
+#[test]
+fn test_keyword_spellings() {
+    let mut env = default_env();
+    // Both spellings read as the same keyword
+    assert_eq!(
+        run("(eq? :name #:name)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(eq? :name (quote name))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_keyword_predicate() {
+    let mut env = default_env();
+    assert_eq!(run("(keyword? :a)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(
+        run("(keyword? (quote a))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_keyword_string_conversion() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(keyword->string :port)", &mut env).unwrap(),
+        Expr::Str("port".into())
+    );
+    assert_eq!(
+        run("(eq? (string->keyword \"port\") :port)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_keyword_type_name() {
+    let result = run("(type-of :a)", &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Symbol("keyword".into()));
+}
+
+#[test]
+fn test_keyword_arguments_with_defaults() {
+    let mut env = default_env();
+    run(
+        "(define f (lambda (x #:key (scale 1) (offset 0)) (+ (* x scale) offset)))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(run("(f 3)", &mut env).unwrap(), Expr::Number(3));
+    assert_eq!(run("(f 3 :scale 2)", &mut env).unwrap(), Expr::Number(6));
+    assert_eq!(
+        run("(f 3 :offset 1 :scale 2)", &mut env).unwrap(),
+        Expr::Number(7)
+    );
+    assert_eq!(
+        run("(f 3 #:offset 10)", &mut env).unwrap(),
+        Expr::Number(13)
+    );
+}
+
+#[test]
+fn test_defaults_see_earlier_parameters() {
+    let mut env = default_env();
+    run("(define box (lambda (w #:key (h w)) (* w h)))", &mut env).unwrap();
+    assert_eq!(run("(box 4)", &mut env).unwrap(), Expr::Number(16));
+    assert_eq!(run("(box 4 :h 2)", &mut env).unwrap(), Expr::Number(8));
+}
+
+#[test]
+fn test_required_keyword() {
+    let mut env = default_env();
+    run(
+        "(define connect (lambda (#:key host (port 80)) port))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(connect :host \"example\")", &mut env).unwrap(),
+        Expr::Number(80)
+    );
+    assert_eq!(
+        run("(connect :port 8080)", &mut env).unwrap_err(),
+        "missing keyword argument :host"
+    );
+}
+
+#[test]
+fn test_keyword_errors() {
+    let mut env = default_env();
+    run(
+        "(define f (lambda (x #:key (scale 1)) (* x scale)))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(f 1 :scael 2)", &mut env).unwrap_err(),
+        "unknown keyword argument :scael"
+    );
+    assert_eq!(
+        run("(f 1 :scale)", &mut env).unwrap_err(),
+        "missing value for keyword argument :scale"
+    );
+    assert_eq!(
+        run("(f 1 2)", &mut env).unwrap_err(),
+        "expected a keyword argument, got 2"
+    );
+    assert_eq!(
+        run("(f 1 :scale 2 :scale 3)", &mut env).unwrap_err(),
+        "keyword argument :scale given more than once"
+    );
+}
+
+#[test]
+fn test_keyword_lambda_errors() {
+    let mut env = default_env();
+    run("(define f (lambda (x #:key (scale 1)) x))", &mut env).unwrap();
+    assert!(run("(f)", &mut env).is_err());
+    assert!(run("(lambda (x #:key (y)) x)", &mut env).is_err());
+}
+
+#[test]
+fn test_keywords_as_positional_values() {
+    // Without keyword parameters, keywords are ordinary arguments
+    let mut env = default_env();
+    run("(define id (lambda (x) x))", &mut env).unwrap();
+    assert_eq!(
+        run("(id :scale)", &mut env).unwrap(),
+        Expr::Keyword("scale".into())
+    );
+}
+
+#[test]
+fn test_keyword_reflection() {
+    let mut env = default_env();
+    run("(define f (lambda (x #:key (scale 1) y) x))", &mut env).unwrap();
+    assert_eq!(
+        run("(procedure-arity f)", &mut env).unwrap(),
+        Expr::Number(1)
+    );
+    assert_eq!(
+        run("(procedure-source f)", &mut env).unwrap().to_string(),
+        "(lambda (x :key (scale 1) y) x)"
+    );
+    assert_eq!(
+        run("f", &mut env).unwrap().to_string(),
+        "<function(x, :scale, :y)>"
+    );
+}
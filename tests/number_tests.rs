@@ -0,0 +1,141 @@
+use lisp_interpreter_rs::*;
+
+fn read(source: &str) -> Result<Expr, ParseError> {
+    parse_program(source).map(|program| program.forms[0].clone())
+}
+
+fn read_error(source: &str) -> String {
+    read(source).unwrap_err().message
+}
+
+#[test]
+fn test_decimal_integers_and_reals() {
+    assert_eq!(read("42"), Ok(Expr::Number(42)));
+    assert_eq!(read("-17"), Ok(Expr::Number(-17)));
+    assert_eq!(read("+8"), Ok(Expr::Number(8)));
+    assert_eq!(read("1.5"), Ok(Expr::Real(1.5)));
+    assert_eq!(read("-.5"), Ok(Expr::Real(-0.5)));
+    assert_eq!(read("2."), Ok(Expr::Real(2.0)));
+}
+
+#[test]
+fn test_exponents() {
+    assert_eq!(read("1e3"), Ok(Expr::Real(1000.0)));
+    assert_eq!(read("2.5E-2"), Ok(Expr::Real(0.025)));
+    assert_eq!(read("-.5e+1"), Ok(Expr::Real(-5.0)));
+    assert_eq!(read_error("1e"), "malformed number: 1e");
+    assert_eq!(read_error("1e+"), "malformed number: 1e+");
+}
+
+#[test]
+fn test_radix_prefixes() {
+    assert_eq!(read("#xFF"), Ok(Expr::Number(255)));
+    assert_eq!(read("#xff"), Ok(Expr::Number(255)));
+    assert_eq!(read("#X-1e"), Ok(Expr::Number(-30)));
+    assert_eq!(read("#b1010"), Ok(Expr::Number(10)));
+    assert_eq!(read("#o777"), Ok(Expr::Number(511)));
+    assert_eq!(read("#d99"), Ok(Expr::Number(99)));
+    assert_eq!(read_error("#b102"), "malformed number: #b102");
+    assert_eq!(read_error("#x1.5"), "malformed number: #x1.5");
+    assert_eq!(read_error("#x"), "malformed number: #x");
+}
+
+#[test]
+fn test_exactness_prefixes() {
+    assert_eq!(read("#i5"), Ok(Expr::Real(5.0)));
+    assert_eq!(read("#e1.0"), Ok(Expr::Number(1)));
+    assert_eq!(read("#e1e3"), Ok(Expr::Number(1000)));
+    assert_eq!(read("#x#iF"), Ok(Expr::Real(15.0)));
+    assert_eq!(read("#e#b101"), Ok(Expr::Number(5)));
+    assert_eq!(read_error("#e1.5"), "#e1.5 has no exact integer value");
+    assert_eq!(read_error("#e#e1"), "malformed number: #e#e1");
+    assert_eq!(read_error("#x#o1"), "malformed number: #x#o1");
+}
+
+#[test]
+fn test_prefixes_before_other_text_are_not_numbers() {
+    for atom in ["#date", "#xml", "#even", "#e#t", "#b2", "#i-"] {
+        assert_eq!(read(atom), Ok(Expr::Symbol(atom.into())), "{}", atom);
+    }
+    let kinds: Vec<TokenKind> = tokenize_spanned("#date\"2024-03-05\"")
+        .iter()
+        .map(|token| token.kind)
+        .collect();
+    assert_eq!(kinds, vec![TokenKind::Atom, TokenKind::String]);
+}
+
+#[test]
+fn test_ratios() {
+    assert_eq!(read("6/3"), Ok(Expr::Number(2)));
+    assert_eq!(read("-1/2"), Ok(Expr::Real(-0.5)));
+    assert_eq!(read("#xA/2"), Ok(Expr::Number(5)));
+    assert_eq!(read_error("1/0"), "malformed number: 1/0");
+    assert_eq!(read_error("1/2/3"), "malformed number: 1/2/3");
+}
+
+#[test]
+fn test_infinities_and_nan() {
+    assert_eq!(read("+inf.0"), Ok(Expr::Real(f64::INFINITY)));
+    assert_eq!(read("-inf.0"), Ok(Expr::Real(f64::NEG_INFINITY)));
+    assert!(matches!(read("+nan.0"), Ok(Expr::Real(x)) if x.is_nan()));
+    // Without a sign they are symbols
+    assert_eq!(read("inf.0"), Ok(Expr::Symbol("inf.0".into())));
+}
+
+#[test]
+fn test_malformed_numbers_are_lexical_errors() {
+    let tokens = tokenize_spanned("(+ 12abc 1)");
+    assert_eq!(tokens[2].kind, TokenKind::Error);
+    let err = read("(+ 12abc 1)").unwrap_err();
+    assert_eq!(err.message, "malformed number: 12abc");
+    assert_eq!((err.span.start, err.span.end), (3, 8));
+    assert_eq!(read_error("1+"), "malformed number: 1+");
+    assert_eq!(read_error("-5x"), "malformed number: -5x");
+    let (_, diagnostics) = parse_recovering("(a 1.2.3 b)");
+    assert_eq!(diagnostics[0].message, "malformed number: 1.2.3");
+}
+
+#[test]
+fn test_symbols_that_are_not_numbers() {
+    for name in ["+", "-", "...", "->x", "+.x", "e5", "a1"] {
+        assert_eq!(read(name), Ok(Expr::Symbol(name.into())), "{}", name);
+    }
+    assert_eq!(read("#t"), Ok(Expr::Bool(true)));
+    assert_eq!(read("#:key"), Ok(Expr::Keyword("key".into())));
+}
+
+#[test]
+fn test_numeric_literals_evaluate() {
+    let mut env = Env::new();
+    let program = parse_program("(+ #xFF #b1 1e1 #e2.0)").unwrap();
+    assert_eq!(eval(&program.forms[0], &mut env), Ok(Expr::Real(268.0)));
+}
+
+#[test]
+fn test_integer_bounds() {
+    assert_eq!(read("9223372036854775807"), Ok(Expr::Number(i64::MAX)));
+    assert_eq!(read("-9223372036854775808"), Ok(Expr::Number(i64::MIN)));
+    assert_eq!(read("#x7FFFFFFFFFFFFFFF"), Ok(Expr::Number(i64::MAX)));
+    assert_eq!(read("#x-8000000000000000"), Ok(Expr::Number(i64::MIN)));
+    assert_eq!(
+        read("-9223372036854775808/2"),
+        Ok(Expr::Number(i64::MIN / 2))
+    );
+    // One past either end is an error whatever the radix
+    for text in [
+        "9223372036854775808",
+        "-9223372036854775809",
+        "#x8000000000000000",
+        "#x-8000000000000001",
+        "#b1000000000000000000000000000000000000000000000000000000000000000",
+        "#o-1000000000000000000001",
+        "9223372036854775808/2",
+    ] {
+        assert_eq!(
+            read_error(text),
+            format!("integer out of range: {}", text),
+            "{}",
+            text
+        );
+    }
+}
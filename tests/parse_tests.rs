@@ -11,7 +11,7 @@ fn test_parse_number() {
 fn test_parse_symbol() {
     let tokens = vec!["foo"];
     let expr = parse(&tokens).unwrap();
-    assert_eq!(expr, Expr::Symbol("foo".to_string()));
+    assert_eq!(expr, Expr::Symbol("foo".into()));
 }
 
 #[test]
@@ -28,7 +28,7 @@ fn test_parse_simple_list() {
     assert_eq!(
         expr,
         Expr::List(vec![
-            Expr::Symbol("+".to_string()),
+            Expr::Symbol("+".into()),
             Expr::Number(1),
             Expr::Number(2)
         ])
@@ -42,9 +42,9 @@ fn test_parse_nested_list() {
     assert_eq!(
         expr,
         Expr::List(vec![
-            Expr::Symbol("+".to_string()),
+            Expr::Symbol("+".into()),
             Expr::List(vec![
-                Expr::Symbol("*".to_string()),
+                Expr::Symbol("*".into()),
                 Expr::Number(2),
                 Expr::Number(3)
             ]),
@@ -91,7 +91,7 @@ fn test_parse_deeply_nested_list() {
     assert_eq!(
         expr,
         Expr::List(vec![Expr::List(vec![Expr::List(vec![Expr::Symbol(
-            "a".to_string()
+            "a".into()
         )])])])
     );
 }
@@ -119,8 +119,8 @@ fn test_parse_mixed_symbols_and_numbers() {
     assert_eq!(
         expr,
         Expr::List(vec![
-            Expr::Symbol("define".to_string()),
-            Expr::Symbol("x".to_string()),
+            Expr::Symbol("define".into()),
+            Expr::Symbol("x".into()),
             Expr::Number(100)
         ])
     );
@@ -133,9 +133,9 @@ fn test_parse_list_with_symbols_only() {
     assert_eq!(
         expr,
         Expr::List(vec![
-            Expr::Symbol("foo".to_string()),
-            Expr::Symbol("bar".to_string()),
-            Expr::Symbol("baz".to_string())
+            Expr::Symbol("foo".into()),
+            Expr::Symbol("bar".into()),
+            Expr::Symbol("baz".into())
         ])
     );
 }
@@ -157,20 +157,20 @@ fn test_parse_complex_nested_expression() {
     assert_eq!(
         expr,
         Expr::List(vec![
-            Expr::Symbol("if".to_string()),
+            Expr::Symbol("if".into()),
             Expr::List(vec![
-                Expr::Symbol(">".to_string()),
-                Expr::Symbol("x".to_string()),
+                Expr::Symbol(">".into()),
+                Expr::Symbol("x".into()),
                 Expr::Number(0)
             ]),
             Expr::List(vec![
-                Expr::Symbol("+".to_string()),
-                Expr::Symbol("x".to_string()),
+                Expr::Symbol("+".into()),
+                Expr::Symbol("x".into()),
                 Expr::Number(1)
             ]),
             Expr::List(vec![
-                Expr::Symbol("-".to_string()),
-                Expr::Symbol("x".to_string()),
+                Expr::Symbol("-".into()),
+                Expr::Symbol("x".into()),
                 Expr::Number(1)
             ])
         ])
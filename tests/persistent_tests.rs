@@ -0,0 +1,268 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_assoc_leaves_original_unchanged() {
+    let mut env = default_env();
+    run(
+        "(define config (hash-map (quote port) 80 (quote host) \"a\"))",
+        &mut env,
+    )
+    .unwrap();
+    run(
+        "(define updated (assoc config (quote port) 8080))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(get config (quote port))", &mut env).unwrap(),
+        Expr::Number(80)
+    );
+    assert_eq!(
+        run("(get updated (quote port))", &mut env).unwrap(),
+        Expr::Number(8080)
+    );
+    assert_eq!(run("(count updated)", &mut env).unwrap(), Expr::Number(2));
+}
+
+#[test]
+fn test_map_get() {
+    let mut env = default_env();
+    run("(define m (hash-map \"a\" 1 \"b\" 2))", &mut env).unwrap();
+    assert_eq!(run("(get m \"a\")", &mut env).unwrap(), Expr::Number(1));
+}
+
+#[test]
+fn test_map_get_missing_key() {
+    let mut env = default_env();
+    run("(define m (hash-map \"a\" 1))", &mut env).unwrap();
+    assert_eq!(run("(get m \"z\")", &mut env).unwrap(), Expr::Bool(false));
+    assert_eq!(run("(get m \"z\" 0)", &mut env).unwrap(), Expr::Number(0));
+}
+
+#[test]
+fn test_map_dissoc_leaves_original_unchanged() {
+    let mut env = default_env();
+    run("(define m (hash-map \"a\" 1 \"b\" 2))", &mut env).unwrap();
+    run("(define smaller (dissoc m \"a\" \"missing\"))", &mut env).unwrap();
+    assert_eq!(
+        run("(contains? smaller \"a\")", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(contains? m \"a\")", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(run("(count smaller)", &mut env).unwrap(), Expr::Number(1));
+}
+
+#[test]
+fn test_hash_map_needs_pairs() {
+    assert!(run("(hash-map 1)", &mut default_env()).is_err());
+}
+
+#[test]
+fn test_alist_first_entry_wins() {
+    let mut env = default_env();
+    run(
+        "(define m (alist->hash-map (quote ((a 1) (b 2) (a 3)))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(run("(get m (quote a))", &mut env).unwrap(), Expr::Number(1));
+    assert_eq!(run("(count m)", &mut env).unwrap(), Expr::Number(2));
+}
+
+#[test]
+fn test_pvector_conj() {
+    let result = run("(conj (pvector 1 2 3) 4 5)", &mut default_env()).unwrap();
+    assert_eq!(result.to_string(), "[1 2 3 4 5]");
+}
+
+#[test]
+fn test_pvector_assoc() {
+    let result = run("(assoc (pvector 1 2 3) 0 10 3 4)", &mut default_env()).unwrap();
+    assert_eq!(result.to_string(), "[10 2 3 4]");
+}
+
+#[test]
+fn test_pvector_pop_leaves_original_unchanged() {
+    let mut env = default_env();
+    run("(define v (pvector 1 2 3))", &mut env).unwrap();
+    assert_eq!(run("(pop v)", &mut env).unwrap().to_string(), "[1 2]");
+    assert_eq!(run("v", &mut env).unwrap().to_string(), "[1 2 3]");
+}
+
+#[test]
+fn test_pvector_get() {
+    let mut env = default_env();
+    run("(define v (pvector 1 2 3))", &mut env).unwrap();
+    assert_eq!(run("(get v 1)", &mut env).unwrap(), Expr::Number(2));
+    assert_eq!(run("(get v 9)", &mut env).unwrap(), Expr::Bool(false));
+}
+
+#[test]
+fn test_pvector_errors() {
+    let mut env = default_env();
+    assert!(run("(assoc (pvector 1 2 3) 5 0)", &mut env).is_err());
+    assert!(run("(pop (pvector))", &mut env).is_err());
+}
+
+#[test]
+fn test_set_drops_duplicates() {
+    let result = run("(count (hash-set 1 2 2 3))", &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Number(3));
+}
+
+#[test]
+fn test_set_disj() {
+    let mut env = default_env();
+    run("(define s (hash-set 1 2 3))", &mut env).unwrap();
+    assert_eq!(run("(contains? s 2)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(
+        run("(contains? (disj s 2) 2)", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_set_conj() {
+    let result = run("(count (conj (hash-set 1 2 3) 3 4))", &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Number(4));
+}
+
+#[test]
+fn test_list_to_hash_set() {
+    let result = run(
+        "(hash-set? (list->hash-set (quote (1 1))))",
+        &mut default_env(),
+    )
+    .unwrap();
+    assert_eq!(result, Expr::Bool(true));
+}
+
+#[test]
+fn test_equal_ignores_construction_order() {
+    let mut env = default_env();
+    assert_eq!(
+        run(
+            "(equal? (hash-map 1 (pvector \"x\") 2 3) (assoc (hash-map 2 3) 1 (pvector \"x\")))",
+            &mut env
+        )
+        .unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(equal? (hash-set 1 2 3) (hash-set 3 2 1))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_equal_compares_values() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(equal? (hash-map 1 2) (hash-map 1 3))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(equal? (pvector 1 2) (pvector 1 2))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_persistent_values_are_not_eqv() {
+    let result = run("(eqv? (pvector 1 2) (pvector 1 2))", &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Bool(false));
+}
+
+#[test]
+fn test_structural_hash_table_keys() {
+    let mut env = default_env();
+    run("(define t (make-hash-table))", &mut env).unwrap();
+    run("(hash-table-set! t (hash-set 1 2) \"found\")", &mut env).unwrap();
+    assert_eq!(
+        run("(hash-table-ref t (hash-set 2 1))", &mut env).unwrap(),
+        Expr::Str("found".into())
+    );
+}
+
+#[test]
+fn test_printing_pvector() {
+    let result = run("(pvector 1 \"a\" #\\b)", &mut default_env()).unwrap();
+    assert_eq!(result.written().to_string(), "[1 \"a\" #\\b]");
+}
+
+#[test]
+fn test_printing_map() {
+    let mut env = default_env();
+    let result = run("(hash-map \"k\" 1)", &mut env).unwrap();
+    assert_eq!(result.written().to_string(), "{\"k\" 1}");
+    assert_eq!(run("(hash-map)", &mut env).unwrap().to_string(), "{}");
+}
+
+#[test]
+fn test_printing_set() {
+    let result = run("(hash-set 7)", &mut default_env()).unwrap();
+    assert_eq!(result.to_string(), "#{7}");
+}
+
+#[test]
+fn test_large_vector() {
+    // Enough items for a three-level trie, then back down to empty
+    let n = 40_000;
+    let mut versions = vec![PersistentVector::new()];
+    for i in 0..n {
+        let next = versions.last().unwrap().push(Expr::Number(i));
+        versions.push(next);
+    }
+    let full = versions.last().unwrap().clone();
+    assert_eq!(full.len(), n as usize);
+    for i in (0..n).step_by(997) {
+        assert_eq!(full.get(i as usize), Some(&Expr::Number(i)));
+        // Earlier versions are untouched
+        assert_eq!(versions[i as usize + 1].len(), i as usize + 1);
+        assert_eq!(versions[i as usize].get(i as usize), None);
+    }
+
+    let changed = full.set(1234, Expr::Bool(true)).unwrap();
+    assert_eq!(changed.get(1234), Some(&Expr::Bool(true)));
+    assert_eq!(full.get(1234), Some(&Expr::Number(1234)));
+
+    let mut v = full;
+    for i in (0..n).rev() {
+        assert_eq!(v.get(i as usize), Some(&Expr::Number(i)));
+        v = v.pop().unwrap();
+        assert_eq!(v.len(), i as usize);
+    }
+    assert!(v.pop().is_none());
+}
+
+#[test]
+fn test_large_map() {
+    let n = 5_000;
+    let mut map = PersistentMap::new();
+    for i in 0..n {
+        map = map.insert(Expr::Number(i), Expr::Number(i * 2));
+    }
+    let full = map.clone();
+    assert_eq!(full.len(), n as usize);
+    assert_eq!(full.iter().count(), n as usize);
+    for i in (0..n).step_by(2) {
+        map = map.remove(&Expr::Number(i));
+    }
+    assert_eq!(map.len(), (n / 2) as usize);
+    for i in 0..n {
+        let expected = if i % 2 == 0 {
+            None
+        } else {
+            Some(&Expr::Number(i * 2))
+        };
+        assert_eq!(map.get(&Expr::Number(i)), expected);
+        assert_eq!(full.get(&Expr::Number(i)), Some(&Expr::Number(i * 2)));
+    }
+    assert_eq!(map.remove(&Expr::Number(0)).len(), map.len());
+}
@@ -0,0 +1,84 @@
+use lisp_interpreter_rs::*;
+
+// Parses a whole program and evaluates its forms in order, returning the
+// value of the last one
+fn run_program(source: &str, env: &mut Env) -> Result<Expr, String> {
+    let program = parse_program(source).map_err(|err| err.to_string())?;
+    env.set_source_map(program.source_map);
+    let mut result = Expr::Unspecified;
+    for form in &program.forms {
+        result = eval(form, env)?;
+    }
+    Ok(result)
+}
+
+#[test]
+fn test_parse_all_multiple_forms() {
+    let tokens = tokenize("(define x 1) x 2");
+    let token_refs: Vec<&str> = tokens.iter().map(|s| s.as_str()).collect();
+    let forms = parse_all(&token_refs).unwrap();
+    assert_eq!(forms.len(), 3);
+    assert_eq!(forms[1], Expr::Symbol("x".into()));
+    assert_eq!(forms[2], Expr::Number(2));
+}
+
+#[test]
+fn test_parse_all_empty_input() {
+    assert_eq!(parse_all(&[]).unwrap(), vec![]);
+    assert_eq!(parse_program("  \n ").unwrap().forms, vec![]);
+}
+
+#[test]
+fn test_parse_all_reports_errors() {
+    let tokens = vec!["1", "(", "+", "2"];
+    assert_eq!(
+        parse_all(&tokens).unwrap_err(),
+        "unclosed list: missing ')'"
+    );
+}
+
+#[test]
+fn test_program_forms_span_lines() {
+    let source = "(define square\n  (lambda (x)\n    (* x x)))\n(square 7)";
+    let mut env = Env::new();
+    assert_eq!(run_program(source, &mut env).unwrap(), Expr::Number(49));
+}
+
+#[test]
+fn test_program_several_forms_on_one_line() {
+    let mut env = Env::new();
+    assert_eq!(
+        run_program("(define a 2) (define b 3) (* a b)", &mut env).unwrap(),
+        Expr::Number(6)
+    );
+}
+
+#[test]
+fn test_program_form_spans() {
+    let program = parse_program("1\n  (f\n x)  \"s\"").unwrap();
+    let starts: Vec<(usize, usize)> = program
+        .spans
+        .iter()
+        .map(|span| (span.line, span.column))
+        .collect();
+    assert_eq!(starts, vec![(1, 1), (2, 3), (3, 6)]);
+    assert_eq!(program.spans[1].end, 10);
+}
+
+#[test]
+fn test_program_parse_error_location() {
+    let err = parse_program("(define x 1)\n(display\n  (+ x 1)").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 1: unclosed list: missing ')'"
+    );
+}
+
+#[test]
+fn test_program_eval_error_location() {
+    let mut env = Env::new();
+    let source = "(define f\n  (lambda (x)\n    (+ x (car x))))\n(f 1)";
+    assert!(run_program(source, &mut env).is_err());
+    let span = env.take_error_span().unwrap();
+    assert_eq!((span.line, span.column), (3, 10));
+}
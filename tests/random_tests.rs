@@ -0,0 +1,156 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+fn draws(env: &mut Env, count: usize) -> Vec<Expr> {
+    (0..count)
+        .map(|_| run("(random-integer 1000000)", env).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_same_seed_same_sequence() {
+    let mut a = default_env();
+    let mut b = default_env();
+    a.set_random_seed(42);
+    b.set_random_seed(42);
+    assert_eq!(draws(&mut a, 20), draws(&mut b, 20));
+}
+
+#[test]
+fn test_different_seeds_differ() {
+    let mut a = default_env();
+    let mut c = default_env();
+    a.set_random_seed(42);
+    c.set_random_seed(43);
+    assert_ne!(draws(&mut a, 20), draws(&mut c, 20));
+}
+
+#[test]
+fn test_random_seed_restarts_sequence() {
+    let mut env = default_env();
+    run("(random-seed! 7)", &mut env).unwrap();
+    let first = draws(&mut env, 5);
+    assert_eq!(
+        run("(random-seed! 7)", &mut env).unwrap(),
+        Expr::Unspecified
+    );
+    assert_eq!(draws(&mut env, 5), first);
+}
+
+#[test]
+fn test_closures_share_the_generator() {
+    // A procedure advances the same sequence as the top level
+    let mut env = default_env();
+    run("(define roll (lambda () (random-integer 6)))", &mut env).unwrap();
+    env.set_random_seed(1);
+    let from_closure = run("(roll)", &mut env).unwrap();
+    let next = run("(random-integer 6)", &mut env).unwrap();
+    env.set_random_seed(1);
+    assert_eq!(run("(random-integer 6)", &mut env).unwrap(), from_closure);
+    assert_eq!(run("(random-integer 6)", &mut env).unwrap(), next);
+}
+
+#[test]
+fn test_random_integer_range() {
+    let mut env = default_env();
+    let mut seen = [false; 6];
+    for _ in 0..500 {
+        match run("(random-integer 6)", &mut env).unwrap() {
+            Expr::Number(n) => seen[n as usize] = true,
+            other => panic!("expected an integer, got {}", other),
+        }
+    }
+    assert!(seen.iter().all(|&s| s));
+}
+
+#[test]
+fn test_random_integer_needs_a_positive_bound() {
+    let mut env = default_env();
+    assert!(run("(random-integer 0)", &mut env).is_err());
+    assert!(run("(random-integer -3)", &mut env).is_err());
+}
+
+#[test]
+fn test_random_real_range() {
+    let mut env = default_env();
+    let mut total = 0.0;
+    for _ in 0..1000 {
+        match run("(random-real)", &mut env).unwrap() {
+            Expr::Real(x) => {
+                assert!(x > 0.0 && x < 1.0);
+                total += x;
+            }
+            other => panic!("expected a real, got {}", other),
+        }
+    }
+    assert!((total / 1000.0 - 0.5).abs() < 0.05);
+}
+
+#[test]
+fn test_shuffle_keeps_items_and_kind() {
+    let mut env = default_env();
+    run("(define v (vector 1 2 3 4 5 6 7 8))", &mut env).unwrap();
+    run("(define s (shuffle v))", &mut env).unwrap();
+    assert_eq!(run("(vector? s)", &mut env).unwrap(), Expr::Bool(true));
+    let mut items: Vec<String> = match run("(vector->list s)", &mut env).unwrap() {
+        Expr::List(items) => items.iter().map(|e| e.to_string()).collect(),
+        other => panic!("expected a list, got {}", other),
+    };
+    items.sort();
+    assert_eq!(items, ["1", "2", "3", "4", "5", "6", "7", "8"]);
+}
+
+#[test]
+fn test_shuffle_copies_vectors() {
+    let mut env = default_env();
+    run("(define v (vector 1 2 3 4 5 6 7 8))", &mut env).unwrap();
+    run("(shuffle v)", &mut env).unwrap();
+    assert_eq!(
+        run("v", &mut env).unwrap().to_string(),
+        "#(1 2 3 4 5 6 7 8)"
+    );
+}
+
+#[test]
+fn test_shuffle_lists_and_pvectors() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(shuffle (quote ()))", &mut env).unwrap(),
+        Expr::List(vec![])
+    );
+    assert_eq!(
+        run("(pvector? (shuffle (pvector 1 2)))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_random_choice() {
+    let mut env = default_env();
+    for _ in 0..20 {
+        let choice = run("(random-choice (quote (a b c)))", &mut env).unwrap();
+        assert!(["a", "b", "c"].contains(&choice.to_string().as_str()));
+    }
+}
+
+#[test]
+fn test_random_choice_errors() {
+    let mut env = default_env();
+    assert!(run("(random-choice (quote ()))", &mut env).is_err());
+    assert!(run("(random-choice 5)", &mut env).is_err());
+}
+
+#[test]
+fn test_reals() {
+    let mut env = default_env();
+    assert_eq!(run("1.5", &mut env).unwrap(), Expr::Real(1.5));
+    assert_eq!(run("(* 2 .25)", &mut env).unwrap(), Expr::Real(0.5));
+    assert_eq!(run("(< 1 1.5 2)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(= 2 2.0)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(eqv? 2 2.0)", &mut env).unwrap(), Expr::Bool(false));
+    assert_eq!(run("(integer? 2.0)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(- 1.0)", &mut env).unwrap().to_string(), "-1.0");
+    assert_eq!(run("(/ 1.0 0)", &mut env).unwrap().to_string(), "+inf.0");
+}
@@ -0,0 +1,181 @@
+use lisp_interpreter_rs::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Reads and evaluates the forms of `source` one at a time, each with the
+// readtable the forms before it left in `env`, returning the last value
+fn run(source: &str, env: &mut Env) -> Result<Expr, String> {
+    let mut reader = IncrementalParser::new();
+    reader.push(source);
+    reader.finish();
+    let mut result = Expr::Unspecified;
+    loop {
+        reader.set_readtable(env.readtable());
+        let Some(parsed) = reader.next() else {
+            return Ok(result);
+        };
+        let (form, _) = parsed.map_err(|err| err.to_string())?;
+        result = eval(&form, env)?;
+    }
+}
+
+#[test]
+fn test_default_syntax_goes_through_the_table() {
+    let table = Readtable::default();
+    for name in ["'", "#(", "#u8("] {
+        assert!(table.contains(name), "missing {}", name);
+    }
+    assert_eq!(table.prefix_chars(), &['\'']);
+    let program = parse_program_with("'a #(1 2) #u8(3)", &table).unwrap();
+    assert_eq!(
+        program.forms,
+        parse_program("'a #(1 2) #u8(3)").unwrap().forms
+    );
+}
+
+#[test]
+fn test_dispatch_macro_registered_from_rust() {
+    let mut table = Readtable::default();
+    table
+        .set("#date", |datum| match datum {
+            Expr::Str(text) => Date::parse_iso8601(&text).map(Expr::Date),
+            other => Err(format!("#date expects a string, got {}", other.written())),
+        })
+        .unwrap();
+    let program = parse_program_with("(date-year #date\"2024-03-05\")", &table).unwrap();
+    let mut env = Env::new();
+    assert_eq!(eval(&program.forms[0], &mut env), Ok(Expr::Number(2024)));
+    // A space between the macro and its datum is allowed
+    let program = parse_program_with("#date \"1999-12-31\"", &table).unwrap();
+    assert!(matches!(program.forms[0], Expr::Date(_)));
+}
+
+#[test]
+fn test_prefix_character_macro() {
+    let mut table = Readtable::default();
+    table
+        .set("`", |datum| {
+            Ok(Expr::List(vec![Expr::Symbol("not".into()), datum]))
+        })
+        .unwrap();
+    assert!(table.prefix_chars().contains(&'`'));
+    let program = parse_program_with("(if `#f 1 2) `(= 1 2)", &table).unwrap();
+    let mut env = Env::new();
+    assert_eq!(eval(&program.forms[0], &mut env), Ok(Expr::Number(1)));
+    assert_eq!(eval(&program.forms[1], &mut env), Ok(Expr::Bool(true)));
+}
+
+#[test]
+fn test_reader_macro_defined_in_lisp() {
+    let mut env = Env::new();
+    let source = "(set-reader-macro! \"#square\" (lambda (n) (* n n)))\n(+ #square 5 1)";
+    assert_eq!(run(source, &mut env), Ok(Expr::Number(26)));
+    assert_eq!(
+        run("(reader-macro? \"#square\")", &mut env),
+        Ok(Expr::Bool(true))
+    );
+    assert_eq!(
+        run(
+            "(set-reader-macro! \"#sym\" string->symbol) '#sym\"abc\"",
+            &mut env
+        ),
+        Ok(Expr::Symbol("abc".into()))
+    );
+}
+
+#[test]
+fn test_lisp_macro_does_not_keep_the_environment_alive() {
+    let bytes = Rc::new(RefCell::new(vec![1, 2]));
+    let mut env = Env::new();
+    env.insert("bytes".into(), Expr::Bytevector(bytes.clone()));
+    let source =
+        "(set-reader-macro! \"#first\" (lambda (d) (bytevector-u8-ref bytes 0)))\n#first x";
+    assert_eq!(run(source, &mut env), Ok(Expr::Number(1)));
+    // The macro's procedure holds a copy of the environment, which must not
+    // hold the readtable the macro lives in
+    drop(env);
+    assert_eq!(Rc::strong_count(&bytes), 1);
+}
+
+#[test]
+fn test_removing_a_macro() {
+    let mut env = Env::new();
+    run("(set-reader-macro! \"#two\" (lambda (x) 2))", &mut env).unwrap();
+    assert_eq!(run("#two x", &mut env), Ok(Expr::Number(2)));
+    assert_eq!(
+        run("(remove-reader-macro! \"#two\")", &mut env),
+        Ok(Expr::Bool(true))
+    );
+    assert_eq!(
+        run("(remove-reader-macro! \"#two\")", &mut env),
+        Ok(Expr::Bool(false))
+    );
+    // With the macro gone, #two is an ordinary symbol again
+    assert!(run("#two", &mut env).unwrap_err().contains("#two"));
+
+    let mut table = Readtable::default();
+    assert!(table.remove("'"));
+    assert!(table.prefix_chars().is_empty());
+    let err = parse_program_with("#(1)", &{
+        let mut table = Readtable::default();
+        table.remove("#(");
+        table
+    })
+    .unwrap_err();
+    assert_eq!(err.message, "unknown reader syntax #(");
+}
+
+#[test]
+fn test_invalid_macro_names() {
+    let mut table = Readtable::default();
+    for name in [
+        "", "a", "1", "(", "\"", ";", "#", "#|x", "#:k", "ab", "#a b", "+", "-", ".", "!", "*",
+        "<", "λ",
+    ] {
+        assert!(table.set(name, Ok).is_err(), "{:?} accepted", name);
+    }
+    for name in ["`", ",", "@", "[", "#date", "#(", "#u8("] {
+        assert!(table.set(name, Ok).is_ok(), "{:?} rejected", name);
+    }
+    let mut env = Env::new();
+    assert!(
+        run("(set-reader-macro! \"x\" (lambda (d) d))", &mut env)
+            .unwrap_err()
+            .contains("invalid reader macro name")
+    );
+    assert!(
+        run("(set-reader-macro! \"#x1\" 5)", &mut env)
+            .unwrap_err()
+            .contains("requires a procedure")
+    );
+}
+
+#[test]
+fn test_handler_errors_are_located() {
+    let mut table = Readtable::default();
+    table
+        .set("#even", |datum| match datum {
+            Expr::Number(n) if n % 2 == 0 => Ok(Expr::Number(n)),
+            other => Err(format!("{} is not even", other)),
+        })
+        .unwrap();
+    let err = parse_program_with("(+ 1\n   #even 3)", &table).unwrap_err();
+    assert_eq!(err.message, "3 is not even");
+    assert_eq!(
+        (err.span.start, err.span.end, err.span.line, err.span.column),
+        (8, 15, 2, 4)
+    );
+    assert_eq!(
+        parse_program("#u8(1 300)").unwrap_err().message,
+        "bytevector literal: 300 is not a byte (0-255)"
+    );
+}
+
+#[test]
+fn test_datum_comments_skip_macro_datums() {
+    let mut table = Readtable::default();
+    table.set("#two", |_| Ok(Expr::Number(2))).unwrap();
+    let program = parse_program_with("(+ #;#two (x y) 1)", &table).unwrap();
+    let mut env = Env::new();
+    assert_eq!(eval(&program.forms[0], &mut env), Ok(Expr::Number(1)));
+}
@@ -0,0 +1,160 @@
+use lisp_interpreter_rs::*;
+
+fn messages(diagnostics: &[ParseError]) -> Vec<(usize, usize, &str)> {
+    diagnostics
+        .iter()
+        .map(|d| (d.span.line, d.span.column, d.message.as_str()))
+        .collect()
+}
+
+#[test]
+fn test_clean_input_has_no_diagnostics() {
+    let source = "(define x 1)\n(+ x 2)";
+    let (program, diagnostics) = parse_recovering(source);
+    assert!(diagnostics.is_empty());
+    let expected = parse_program(source).unwrap();
+    assert_eq!(program.forms, expected.forms);
+    assert_eq!(program.spans, expected.spans);
+}
+
+#[test]
+fn test_missing_close_paren_at_end() {
+    let (program, diagnostics) = parse_recovering("(+ 1 (* 2 3)");
+    assert_eq!(
+        messages(&diagnostics),
+        vec![(1, 1, "unclosed list: missing ')'")]
+    );
+    assert_eq!(program.forms.len(), 1);
+    assert_eq!(program.spans[0].end, 12);
+    let mut env = Env::new();
+    assert_eq!(eval(&program.forms[0], &mut env).unwrap(), Expr::Number(7));
+}
+
+#[test]
+fn test_missing_close_paren_before_next_top_level_form() {
+    let source = "(define (f x)\n  (+ x 1)\n(define y 2)\n(oops";
+    let (program, diagnostics) = parse_recovering(source);
+    assert_eq!(
+        messages(&diagnostics),
+        vec![
+            (1, 1, "unclosed list: missing ')'"),
+            (4, 1, "unclosed list: missing ')'"),
+        ]
+    );
+    assert_eq!(program.forms.len(), 3);
+    assert_eq!(program.spans[1].line, 3);
+}
+
+#[test]
+fn test_extra_close_parens_are_skipped() {
+    let (program, diagnostics) = parse_recovering("(+ 1 2))\n) 3");
+    assert_eq!(
+        messages(&diagnostics),
+        vec![
+            (1, 8, "unexpected ')' with no list to close"),
+            (2, 1, "unexpected ')' with no list to close"),
+        ]
+    );
+    assert_eq!(program.forms.len(), 2);
+    assert_eq!(program.forms[1], Expr::Number(3));
+}
+
+#[test]
+fn test_unterminated_string_ends_at_its_line() {
+    let source = "(display \"oops)\n(define x 1)";
+    let (program, diagnostics) = parse_recovering(source);
+    assert_eq!(
+        messages(&diagnostics),
+        vec![
+            (1, 1, "unclosed list: missing ')'"),
+            (1, 10, "unterminated string literal"),
+        ]
+    );
+    assert_eq!(program.forms.len(), 2);
+    assert_eq!(
+        program.forms[0],
+        Expr::List(vec![
+            Expr::Symbol("display".into()),
+            Expr::Error("unterminated string literal".into()),
+        ])
+    );
+    assert_eq!(program.spans[1].line, 2);
+}
+
+#[test]
+fn test_bad_tokens_become_error_nodes() {
+    let (program, diagnostics) = parse_recovering("(list \"\\q\" #\\bogus 1)");
+    assert_eq!(
+        messages(&diagnostics),
+        vec![
+            (1, 7, "unknown escape in string: \\q"),
+            (1, 12, "unknown character name: #\\bogus"),
+        ]
+    );
+    let Expr::List(items) = &program.forms[0] else {
+        panic!("expected a list");
+    };
+    assert!(matches!(items[1], Expr::Error(_)));
+    assert!(matches!(items[2], Expr::Error(_)));
+    assert_eq!(items[3], Expr::Number(1));
+}
+
+#[test]
+fn test_quote_without_datum() {
+    let (program, diagnostics) = parse_recovering("(a ') 'b '");
+    assert_eq!(
+        messages(&diagnostics),
+        vec![
+            (1, 4, "missing datum after '"),
+            (1, 10, "missing datum after '"),
+        ]
+    );
+    assert_eq!(program.forms.len(), 3);
+}
+
+#[test]
+fn test_error_nodes_fail_when_evaluated() {
+    let (program, _) = parse_recovering("(+ 1 \"abc");
+    let mut env = Env::new();
+    assert_eq!(
+        eval(&program.forms[0], &mut env).unwrap_err(),
+        "unterminated string literal"
+    );
+    assert_eq!(Expr::Error("bad".into()).to_string(), "#<error bad>");
+}
+
+#[test]
+fn test_reader_macros_from_the_given_table() {
+    let mut table = Readtable::default();
+    table
+        .set("#even", |datum| match datum {
+            Expr::Number(n) => Ok(Expr::Bool(n % 2 == 0)),
+            other => Err(format!("#even expects a number, got {}", other)),
+        })
+        .unwrap();
+    table
+        .set("`", |datum| {
+            Ok(Expr::List(vec![Expr::Symbol("not".into()), datum]))
+        })
+        .unwrap();
+    let (program, diagnostics) =
+        parse_recovering_with("(list #even 4 `#t) (#even \"a\" #even)", &table);
+    assert_eq!(
+        messages(&diagnostics),
+        vec![
+            (1, 21, "#even expects a number, got a"),
+            (1, 31, "missing datum after #even"),
+        ]
+    );
+    assert_eq!(
+        program.forms[0],
+        Expr::List(vec![
+            Expr::Symbol("list".into()),
+            Expr::Bool(true),
+            Expr::List(vec![Expr::Symbol("not".into()), Expr::Bool(true)]),
+        ])
+    );
+    // Without the table #even is just a symbol
+    let (_, diagnostics) = parse_recovering("(list #even 4)");
+    assert!(diagnostics.is_empty());
+}
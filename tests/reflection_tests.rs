@@ -0,0 +1,107 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+fn check(input: &str, expected: &str) {
+    let mut env = default_env();
+    let result = run(input, &mut env).unwrap();
+    assert_eq!(result.written().to_string(), expected, "{}", input);
+}
+
+#[test]
+fn test_type_predicates() {
+    check("(number? 1)", "#t");
+    check("(number? \"1\")", "#f");
+    check("(integer? -7)", "#t");
+    check("(boolean? #f)", "#t");
+    check("(boolean? 0)", "#f");
+    check("(symbol? (quote a))", "#t");
+    check("(procedure? +)", "#t");
+    check("(procedure? (lambda (x) x))", "#t");
+    check("(procedure? (quote +))", "#f");
+    check("(list? (quote (1 2)))", "#t");
+    check("(list? #(1 2))", "#f");
+    check("(null? (quote ()))", "#t");
+    check("(null? (quote (1)))", "#f");
+}
+
+#[test]
+fn test_type_of() {
+    check("(type-of 1)", "number");
+    check("(type-of #t)", "boolean");
+    check("(type-of (quote x))", "symbol");
+    check("(type-of \"s\")", "string");
+    check("(type-of #\\a)", "char");
+    check("(type-of (quote (1)))", "list");
+    check("(type-of #(1))", "vector");
+    check("(type-of #u8(1))", "bytevector");
+    check("(type-of (make-hash-table))", "hash-table");
+    check("(type-of +)", "procedure");
+    check("(type-of (lambda () 1))", "procedure");
+    check("(type-of (vector-set! (vector 1) 0 2))", "unspecified");
+    check("(type-of (eof-object))", "eof-object");
+}
+
+#[test]
+fn test_procedure_arity() {
+    check("(procedure-arity (lambda (a b c) a))", "3");
+    check("(procedure-arity (lambda () 1))", "0");
+    check("(procedure-arity +)", "#f");
+    assert!(run("(procedure-arity 5)", &mut default_env()).is_err());
+}
+
+#[test]
+fn test_procedure_name() {
+    let mut env = default_env();
+    run("(define square (lambda (x) (* x x)))", &mut env).unwrap();
+    run("(define also-square square)", &mut env).unwrap();
+    assert_eq!(
+        run("(procedure-name square)", &mut env).unwrap(),
+        Expr::Symbol("square".into())
+    );
+    // A procedure keeps the name it was first defined with
+    assert_eq!(
+        run("(procedure-name also-square)", &mut env).unwrap(),
+        Expr::Symbol("square".into())
+    );
+    assert_eq!(
+        run("(procedure-name (lambda (x) x))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(procedure-name vector-ref)", &mut env).unwrap(),
+        Expr::Symbol("vector-ref".into())
+    );
+}
+
+#[test]
+fn test_procedure_source() {
+    let mut env = default_env();
+    run("(define add (lambda (x y) (+ x y)))", &mut env).unwrap();
+    assert_eq!(
+        run("(procedure-source add)", &mut env).unwrap().to_string(),
+        "(lambda (x y) (+ x y))"
+    );
+    assert_eq!(
+        run("(procedure-source +)", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_validate_before_calling() {
+    // Typical plugin check: only call procedures that take one argument
+    let mut env = default_env();
+    run("(define plugin (lambda (x) (* x 10)))", &mut env).unwrap();
+    run(
+        "(define safe-call (lambda (f x) (if (procedure? f) (if (= (procedure-arity f) 1) (f x) 0) 0)))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(safe-call plugin 4)", &mut env).unwrap(),
+        Expr::Number(40)
+    );
+    assert_eq!(run("(safe-call 7 4)", &mut env).unwrap(), Expr::Number(0));
+}
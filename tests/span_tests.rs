@@ -0,0 +1,172 @@
+use lisp_interpreter_rs::*;
+
+// Parses and evaluates `input`, registering its spans with `env`
+fn run(input: &str, env: &mut Env) -> Result<Expr, String> {
+    let tokens = tokenize_spanned(input);
+    let (expr, source_map) = parse_spanned(&tokens).map_err(|err| err.to_string())?;
+    env.set_source_map(source_map);
+    eval(&expr, env)
+}
+
+fn span(start: usize, end: usize, line: usize, column: usize) -> Span {
+    Span {
+        start,
+        end,
+        line,
+        column,
+    }
+}
+
+#[test]
+fn test_token_kinds() {
+    let kinds: Vec<TokenKind> = tokenize_spanned("(f #(1) #u8(2) 'x \"s\" #\\a)")
+        .iter()
+        .map(|token| token.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::LParen,
+            TokenKind::Atom,
+            TokenKind::VectorOpen,
+            TokenKind::Atom,
+            TokenKind::RParen,
+            TokenKind::BytevectorOpen,
+            TokenKind::Atom,
+            TokenKind::RParen,
+            TokenKind::Quote,
+            TokenKind::Atom,
+            TokenKind::String,
+            TokenKind::Char,
+            TokenKind::RParen,
+        ]
+    );
+}
+
+#[test]
+fn test_token_spans_track_lines_and_columns() {
+    let tokens = tokenize_spanned("(define x\n  \"a b\")");
+    assert_eq!(tokens[0].span, span(0, 1, 1, 1));
+    assert_eq!(tokens[1].span, span(1, 7, 1, 2));
+    assert_eq!(tokens[2].span, span(8, 9, 1, 9));
+    assert_eq!(tokens[3].text, "\"a b\"");
+    assert_eq!(tokens[3].span, span(12, 17, 2, 3));
+    assert_eq!(tokens[4].span, span(17, 18, 2, 8));
+}
+
+#[test]
+fn test_columns_count_characters_not_bytes() {
+    let tokens = tokenize_spanned("(\"λλ\" x)");
+    assert_eq!(tokens[2].text, "x");
+    // Each λ is two bytes but one column
+    assert_eq!(tokens[2].span, span(8, 9, 1, 7));
+}
+
+#[test]
+fn test_tokenize_matches_spanned_text() {
+    let source = "(list #\\( \"a)\" #(1 2) 'y)";
+    let texts: Vec<String> = tokenize_spanned(source)
+        .iter()
+        .map(|token| token.text.to_string())
+        .collect();
+    assert_eq!(tokenize(source), texts);
+}
+
+#[test]
+fn test_quote_shorthand() {
+    let mut env = Env::new();
+    assert_eq!(
+        run("'(1 2)", &mut env).unwrap(),
+        Expr::List(vec![Expr::Number(1), Expr::Number(2)])
+    );
+    assert_eq!(run("'x", &mut env).unwrap(), Expr::Symbol("x".into()));
+}
+
+#[test]
+fn test_parse_error_spans() {
+    let err = parse_spanned(&tokenize_spanned("(+ 1\n  (* 2")).unwrap_err();
+    assert_eq!(err.message, "unclosed list: missing ')'");
+    assert_eq!((err.span.line, err.span.column), (2, 3));
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 3: unclosed list: missing ')'"
+    );
+
+    let err = parse_spanned(&tokenize_spanned("(f \"a\\q\")")).unwrap_err();
+    assert_eq!(err.span, span(3, 8, 1, 4));
+
+    let err = parse_spanned(&tokenize_spanned("1 2")).unwrap_err();
+    assert_eq!(err.message, "unexpected token '2' after expression");
+    assert_eq!(err.span, span(2, 3, 1, 3));
+}
+
+#[test]
+fn test_source_map_records_lists() {
+    let tokens = tokenize_spanned("(a\n (b c))");
+    let (expr, source_map) = parse_spanned(&tokens).unwrap();
+    let Expr::List(items) = &expr else {
+        panic!("expected a list");
+    };
+    assert_eq!(source_map.span_of(items), Some(span(0, 10, 1, 1)));
+    let Expr::List(inner) = &items[1] else {
+        panic!("expected a list");
+    };
+    assert_eq!(source_map.span_of(inner), Some(span(4, 9, 2, 2)));
+}
+
+#[test]
+fn test_eval_error_points_at_innermost_form() {
+    let mut env = Env::new();
+    let err = run("(+ 1\n   (vector-ref (vector) 0))", &mut env).unwrap_err();
+    assert!(err.contains("out of range"), "{}", err);
+    let span = env.take_error_span().unwrap();
+    assert_eq!((span.line, span.column), (2, 4));
+    // Taking the span clears it
+    assert_eq!(env.take_error_span(), None);
+}
+
+#[test]
+fn test_eval_error_inside_lambda_body() {
+    let mut env = Env::new();
+    run("(define f (lambda (x) (+ x (undefined-fn x))))", &mut env).unwrap();
+    run("(f 1)", &mut env).unwrap_err();
+    let span = env.take_error_span().unwrap();
+    assert_eq!((span.line, span.column), (1, 28));
+}
+
+#[test]
+fn test_success_clears_stale_error_span() {
+    let mut env = Env::new();
+    run("(car 1)", &mut env).unwrap_err();
+    run("(+ 1 2)", &mut env).unwrap();
+    assert_eq!(env.take_error_span(), None);
+}
+
+#[test]
+fn test_spans_of_freed_lists_are_forgotten() {
+    let mut env = Env::new();
+    let (expr, source_map) = parse_spanned(&tokenize_spanned("(car 1)")).unwrap();
+    env.set_source_map(source_map);
+    eval(&expr, &mut env).unwrap_err();
+    assert!(env.take_error_span().is_some());
+    // Reuse the list's buffer for code that was not read from source, as the
+    // allocator may once the parsed form is freed
+    let Expr::List(mut items) = expr else {
+        panic!("expected a list");
+    };
+    items.clear();
+    items.extend([Expr::Symbol("car".into()), Expr::Number(2)]);
+    env.set_source_map(SourceMap::new());
+    eval(&Expr::List(items), &mut env).unwrap_err();
+    assert_eq!(env.take_error_span(), None);
+}
+
+#[test]
+fn test_lambda_keeps_spans_after_its_source_is_freed() {
+    let mut env = Env::new();
+    run("(define f (lambda (x)\n  (car x)))", &mut env).unwrap();
+    run("(+ 1 2)", &mut env).unwrap();
+    run("(f 1)", &mut env).unwrap_err();
+    let span = env.take_error_span().unwrap();
+    assert_eq!((span.line, span.column), (2, 3));
+}
@@ -0,0 +1,103 @@
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_token_slices_borrow_the_source() {
+    let source = "(define s \"a b\") ; note\n#(1 2)";
+    let slices: Vec<&str> = tokenize_slices(source).collect();
+    assert_eq!(slices, tokenize(source));
+    let range = source.as_bytes().as_ptr_range();
+    for slice in slices {
+        assert!(range.contains(&slice.as_ptr()), "{:?} was copied", slice);
+    }
+}
+
+#[test]
+fn test_parse_takes_slices() {
+    let tokens = ["(", "+", "1", "2", ")"];
+    assert_eq!(
+        parse(&tokens),
+        Ok(Expr::List(vec![
+            Expr::Symbol("+".into()),
+            Expr::Number(1),
+            Expr::Number(2),
+        ]))
+    );
+    let tokens: Vec<&str> = tokenize_slices("1 (x) 'y").collect();
+    assert_eq!(parse_all(&tokens).unwrap().len(), 3);
+}
+
+#[test]
+fn test_parser_reads_forms_from_a_lexer() {
+    let source = "(define x 4)\n'(a b)\n\"text\" #(1 2)";
+    let parsed: Vec<(Expr, Span)> = Parser::new(Lexer::new(source))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let program = parse_program(source).unwrap();
+    let (forms, spans): (Vec<Expr>, Vec<Span>) = parsed.into_iter().unzip();
+    assert_eq!(forms, program.forms);
+    assert_eq!(spans, program.spans);
+}
+
+#[test]
+fn test_parser_is_lazy() {
+    // Only the tokens of the forms asked for are read
+    let mut lexer = Lexer::new("(a b) c (d");
+    let mut forms = Parser::new(lexer.by_ref());
+    assert!(forms.next().unwrap().is_ok());
+    drop(forms);
+    assert_eq!(lexer.next().map(|token| token.text), Some("c"));
+}
+
+#[test]
+fn test_parser_stops_after_an_error() {
+    let mut forms = Parser::new(Lexer::new("1 (2 12abc) 3"));
+    assert_eq!(forms.next(), Some(Ok((Expr::Number(1), parse_span(0, 1)))));
+    let err = forms.next().unwrap().unwrap_err();
+    assert_eq!(err.message, "malformed number: 12abc");
+    assert_eq!(forms.next(), None);
+
+    let mut forms = Parser::new(Lexer::new("(1 2"));
+    let err = forms.next().unwrap().unwrap_err();
+    assert_eq!(err.message, "unclosed list: missing ')'");
+    assert_eq!(forms.next(), None);
+}
+
+fn parse_span(start: usize, end: usize) -> Span {
+    Span {
+        start,
+        end,
+        line: 1,
+        column: start + 1,
+    }
+}
+
+#[test]
+fn test_parser_with_readtable_and_source_map() {
+    let mut table = Readtable::default();
+    table.set("#two", |_| Ok(Expr::Number(2))).unwrap();
+    // #two reads x as its datum, and the whole of #two x reads as 2
+    let source = "(+ #two x)\n(f (g))";
+    let mut forms = Parser::new(Lexer::new(source).with_readtable(&table)).with_readtable(&table);
+    let (first, _) = forms.next().unwrap().unwrap();
+    assert_eq!(
+        first,
+        Expr::List(vec![Expr::Symbol("+".into()), Expr::Number(2)])
+    );
+    let (second, _) = forms.next().unwrap().unwrap();
+    let source_map = forms.take_source_map();
+    assert_eq!(source_map.len(), 3);
+    let Expr::List(items) = &second else {
+        panic!("expected a list");
+    };
+    assert_eq!(source_map.span_of(items).map(|span| span.line), Some(2));
+}
+
+#[test]
+fn test_large_inputs() {
+    let source = "(item 1 \"two\" #(3))\n".repeat(5_000);
+    assert_eq!(Parser::new(Lexer::new(&source)).count(), 5_000);
+    let mut reader = IncrementalParser::new();
+    reader.push(&source);
+    reader.finish();
+    assert_eq!(reader.filter(Result::is_ok).count(), 5_000);
+}
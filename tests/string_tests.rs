@@ -0,0 +1,167 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+fn string(s: &str) -> Expr {
+    Expr::Str(s.into())
+}
+
+#[test]
+fn test_string_literal_is_self_evaluating() {
+    let mut env = default_env();
+    assert_eq!(
+        run("\"hello world\"", &mut env).unwrap(),
+        string("hello world")
+    );
+}
+
+#[test]
+fn test_string_length_counts_characters() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(string-length \"hello\")", &mut env).unwrap(),
+        Expr::Number(5)
+    );
+    assert_eq!(
+        run("(string-length \"\\x3bb;x\")", &mut env).unwrap(),
+        Expr::Number(2)
+    );
+}
+
+#[test]
+fn test_string_append() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(string-append \"foo\" \" \" \"bar\")", &mut env).unwrap(),
+        string("foo bar")
+    );
+    assert_eq!(run("(string-append)", &mut env).unwrap(), string(""));
+}
+
+#[test]
+fn test_string_append_type_error() {
+    assert!(run("(string-append \"a\" 1)", &mut default_env()).is_err());
+}
+
+#[test]
+fn test_substring() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(substring \"hello world\" 6 11)", &mut env).unwrap(),
+        string("world")
+    );
+    assert_eq!(
+        run("(substring \"hello\" 1)", &mut env).unwrap(),
+        string("ello")
+    );
+}
+
+#[test]
+fn test_substring_range_errors() {
+    let mut env = default_env();
+    assert!(run("(substring \"hello\" 3 2)", &mut env).is_err());
+    assert!(run("(substring \"hello\" 0 6)", &mut env).is_err());
+}
+
+#[test]
+fn test_string_ref() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(string-ref \"abc\" 1)", &mut env).unwrap(),
+        Expr::Char('b')
+    );
+    assert_eq!(
+        run("(string-ref \"abc\" 3)", &mut env).unwrap_err(),
+        "string-ref index 3 out of bounds for string of length 3"
+    );
+}
+
+#[test]
+fn test_string_comparisons() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(string=? \"abc\" \"abc\" \"abc\")", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(string=? \"abc\" \"abd\")", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(string<? \"apple\" \"banana\" \"cherry\")", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(string<? \"b\" \"a\")", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_string_to_list() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(string->list \"ab\")", &mut env).unwrap(),
+        Expr::List(vec![Expr::Char('a'), Expr::Char('b')])
+    );
+}
+
+#[test]
+fn test_list_to_string() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(list->string (string->list \"hello\"))", &mut env).unwrap(),
+        string("hello")
+    );
+    assert!(run("(list->string (quote (1 2)))", &mut env).is_err());
+}
+
+#[test]
+fn test_number_to_string() {
+    let mut env = default_env();
+    assert_eq!(run("(number->string 42)", &mut env).unwrap(), string("42"));
+    assert_eq!(
+        run("(number->string 255 16)", &mut env).unwrap(),
+        string("ff")
+    );
+    assert_eq!(
+        run("(number->string -5 2)", &mut env).unwrap(),
+        string("-101")
+    );
+}
+
+#[test]
+fn test_string_equality_predicates() {
+    let mut env = default_env();
+    run("(define s \"text\")", &mut env).unwrap();
+    assert_eq!(run("(eq? s s)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(
+        run("(eqv? (string-append \"a\" \"b\") \"ab\")", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(equal? (string-append \"a\" \"b\") \"ab\")", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_display_and_write() {
+    let expr = string("say \"hi\"\n");
+    assert_eq!(expr.to_string(), "say \"hi\"\n");
+    assert_eq!(expr.written().to_string(), "\"say \\\"hi\\\"\\n\"");
+
+    let list = Expr::List(vec![string("a b"), Expr::Number(1)]);
+    assert_eq!(list.to_string(), "(a b 1)");
+    assert_eq!(list.written().to_string(), "(\"a b\" 1)");
+}
+
+#[test]
+fn test_written_string_round_trips() {
+    let original = string("tab\there \\ \"quoted\" \u{7}");
+    let text = original.written().to_string();
+    let tokens = tokenize(&text);
+    let token_refs: Vec<&str> = tokens.iter().map(|s| s.as_str()).collect();
+    assert_eq!(parse(&token_refs).unwrap(), original);
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_interned_symbols_share_storage() {
+    let a = Symbol::intern("shared-name");
+    let b: Symbol = "shared-name".into();
+    let c: Symbol = String::from("shared-name").into();
+    assert_eq!(a, b);
+    assert_eq!(b, c);
+    assert!(std::ptr::eq(a.as_str(), b.as_str()));
+    assert!(a.is_interned());
+    assert_ne!(a, Symbol::intern("other-name"));
+}
+
+#[test]
+fn test_symbol_compares_with_str() {
+    let s = Symbol::intern("abc");
+    assert_eq!(s, "abc");
+    assert_eq!(s.as_str(), "abc");
+    assert_eq!(s.to_string(), "abc");
+    assert_eq!(s.len(), 3);
+}
+
+#[test]
+fn test_gensym_is_unique_and_uninterned() {
+    let a = Symbol::gensym("tmp");
+    let b = Symbol::gensym("tmp");
+    assert_ne!(a, b);
+    assert!(a.as_str().starts_with("tmp"));
+    assert!(!a.is_interned());
+    // Even a symbol interned under the same name is a different symbol
+    assert_ne!(a, Symbol::intern(a.as_str()));
+}
+
+#[test]
+fn test_symbol_predicate() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(symbol? (quote foo))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(symbol? \"foo\")", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
+
+#[test]
+fn test_symbol_to_string() {
+    let result = run("(symbol->string (quote hello))", &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Str("hello".into()));
+}
+
+#[test]
+fn test_string_to_symbol_interns() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(string->symbol \"hello\")", &mut env).unwrap(),
+        Expr::Symbol("hello".into())
+    );
+    assert_eq!(
+        run("(eq? (string->symbol \"abc\") (quote abc))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_symbol_conversion_type_errors() {
+    let mut env = default_env();
+    assert!(run("(symbol->string \"abc\")", &mut env).is_err());
+    assert!(run("(string->symbol (quote abc))", &mut env).is_err());
+}
+
+#[test]
+fn test_gensym_builtin() {
+    let mut env = default_env();
+    run("(define a (gensym))", &mut env).unwrap();
+    run("(define b (gensym \"loop\"))", &mut env).unwrap();
+    assert_eq!(run("(symbol? a)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(eq? a a)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(eq? a b)", &mut env).unwrap(), Expr::Bool(false));
+    // Not eq? to the interned symbol with the same name
+    assert_eq!(
+        run("(eq? a (string->symbol (symbol->string a)))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    match run("b", &mut env).unwrap() {
+        Expr::Symbol(s) => assert!(s.as_str().starts_with("loop")),
+        other => panic!("Expected Symbol, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_symbols_as_hash_table_keys() {
+    let mut env = default_env();
+    run("(define t (make-hash-table eq?))", &mut env).unwrap();
+    run("(hash-table-set! t (quote k) 1)", &mut env).unwrap();
+    assert_eq!(
+        run("(hash-table-ref t (string->symbol \"k\"))", &mut env).unwrap(),
+        Expr::Number(1)
+    );
+}
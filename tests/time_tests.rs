@@ -0,0 +1,218 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_current_time_is_after_2020() {
+    let mut env = default_env();
+    match run("(current-time)", &mut env).unwrap() {
+        Expr::Number(seconds) => assert!(seconds > 1_577_836_800),
+        other => panic!("expected a number, got {}", other),
+    }
+}
+
+#[test]
+fn test_current_date() {
+    let result = run("(date? (current-date))", &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Bool(true));
+}
+
+#[test]
+fn test_jiffies_are_monotonic() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(jiffies-per-second)", &mut env).unwrap(),
+        Expr::Number(1_000_000)
+    );
+    let first = run("(current-jiffy)", &mut env).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    let second = run("(current-jiffy)", &mut env).unwrap();
+    match (first, second) {
+        (Expr::Number(a), Expr::Number(b)) => assert!(b - a >= 2_000),
+        other => panic!("expected numbers, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_date_fields() {
+    let mut env = default_env();
+    run("(define d (make-date 2024 2 29 13 5 9))", &mut env).unwrap();
+    assert_eq!(run("(date-year d)", &mut env).unwrap(), Expr::Number(2024));
+    assert_eq!(run("(date-month d)", &mut env).unwrap(), Expr::Number(2));
+    assert_eq!(run("(date-day d)", &mut env).unwrap(), Expr::Number(29));
+    assert_eq!(run("(date-hour d)", &mut env).unwrap(), Expr::Number(13));
+    assert_eq!(run("(date-minute d)", &mut env).unwrap(), Expr::Number(5));
+    assert_eq!(run("(date-second d)", &mut env).unwrap(), Expr::Number(9));
+}
+
+#[test]
+fn test_date_week_day() {
+    // 2024-02-29 was a Thursday
+    let result = run("(date-week-day (make-date 2024 2 29))", &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Number(4));
+}
+
+#[test]
+fn test_date_to_seconds() {
+    let result = run("(date->seconds (make-date 1970 1 1))", &mut default_env()).unwrap();
+    assert_eq!(result, Expr::Number(0));
+}
+
+#[test]
+fn test_invalid_dates_are_rejected() {
+    let mut env = default_env();
+    assert!(run("(make-date 2023 2 29)", &mut env).is_err());
+    assert!(run("(make-date 2024 13 1)", &mut env).is_err());
+    assert!(run("(make-date 2024 1 1 24 0 0)", &mut env).is_err());
+    assert!(run("(make-date 2024 1)", &mut env).is_err());
+}
+
+#[test]
+fn test_invalid_date_strings_are_rejected() {
+    let mut env = default_env();
+    assert!(run("(string->date \"2024-1-01\")", &mut env).is_err());
+    assert!(run("(string->date \"2024-01-01T10\")", &mut env).is_err());
+}
+
+#[test]
+fn test_date_to_iso8601() {
+    let result = run(
+        "(date->string (make-date 1999 12 31 23 59 59))",
+        &mut default_env(),
+    )
+    .unwrap();
+    assert_eq!(result, Expr::Str("1999-12-31T23:59:59Z".into()));
+}
+
+#[test]
+fn test_iso8601_round_trip() {
+    let mut env = default_env();
+    assert_eq!(
+        run(
+            "(date->string (string->date \"2000-03-01T08:30:00Z\"))",
+            &mut env
+        )
+        .unwrap(),
+        Expr::Str("2000-03-01T08:30:00Z".into())
+    );
+}
+
+#[test]
+fn test_iso8601_round_trip_at_year_bounds() {
+    let mut env = default_env();
+    for (fields, text) in [
+        ("0 1 1", "0000-01-01T00:00:00Z"),
+        ("9999 12 31 23 59 59", "9999-12-31T23:59:59Z"),
+        ("-1 12 31", "-00001-12-31T00:00:00Z"),
+        ("10000 1 1", "+10000-01-01T00:00:00Z"),
+        ("-1000000000 1 1", "-1000000000-01-01T00:00:00Z"),
+        ("1000000000 12 31 23 59 59", "+1000000000-12-31T23:59:59Z"),
+    ] {
+        run(&format!("(define d (make-date {}))", fields), &mut env).unwrap();
+        assert_eq!(
+            run("(date->string d)", &mut env).unwrap(),
+            Expr::Str(text.into())
+        );
+        assert_eq!(
+            run(&format!("(equal? (string->date \"{}\") d)", text), &mut env).unwrap(),
+            Expr::Bool(true)
+        );
+    }
+    assert!(run("(string->date \"+2024-01-01\")", &mut env).is_err());
+    assert!(run("(string->date \"+1000000001-01-01\")", &mut env).is_err());
+}
+
+#[test]
+fn test_iso8601_offsets_and_dates() {
+    let mut env = default_env();
+    // Offsets are converted to UTC and fractions of a second are dropped
+    assert_eq!(
+        run(
+            "(date->string (string->date \"2000-03-01T01:30:00.250-07:00\"))",
+            &mut env
+        )
+        .unwrap(),
+        Expr::Str("2000-03-01T08:30:00Z".into())
+    );
+    assert_eq!(
+        run("(date->string (string->date \"1969-07-20\"))", &mut env).unwrap(),
+        Expr::Str("1969-07-20T00:00:00Z".into())
+    );
+}
+
+#[test]
+fn test_duration_arithmetic() {
+    let mut env = default_env();
+    run("(define start (make-date 2023 12 31 23 0 0))", &mut env).unwrap();
+    run("(define later (date-add start (* 2 3600)))", &mut env).unwrap();
+    assert_eq!(
+        run("(date->string later)", &mut env).unwrap(),
+        Expr::Str("2024-01-01T01:00:00Z".into())
+    );
+    assert_eq!(
+        run("(date-difference later start)", &mut env).unwrap(),
+        Expr::Number(7200)
+    );
+    assert_eq!(
+        run("(equal? (date-add later -7200) start)", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_calendar_matches_days_since_epoch() {
+    // Walk a day at a time across several leap years and centuries and check
+    // the fields round-trip
+    let mut seconds = Date::from_fields(DateFields {
+        year: 1895,
+        month: 1,
+        day: 1,
+        hour: 12,
+        minute: 0,
+        second: 0,
+    })
+    .unwrap()
+    .seconds();
+    for _ in 0..(366 * 210) {
+        let date = Date::from_seconds(seconds);
+        assert_eq!(Date::from_fields(date.fields()), Ok(date));
+        seconds += 86_400;
+    }
+    assert_eq!(Date::from_seconds(seconds).fields().year, 2105);
+}
+
+#[test]
+fn test_dates_print() {
+    let result = run("(make-date 2024 6 1)", &mut default_env()).unwrap();
+    assert_eq!(result.to_string(), "#<date 2024-06-01T00:00:00Z>");
+}
+
+#[test]
+fn test_date_type_name() {
+    let result = run("(type-of (make-date 2024 6 1))", &mut default_env()).unwrap();
+    assert_eq!(result.to_string(), "date");
+}
+
+#[test]
+fn test_out_of_range_dates_are_errors() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(make-date 9223372036854775807 1 1)", &mut env).unwrap_err(),
+        "make-date: year 9223372036854775807 out of range -1000000000 to 1000000000"
+    );
+    assert!(run("(make-date -9223372036854775808 1 1)", &mut env).is_err());
+}
+
+#[test]
+fn test_date_difference_overflow() {
+    let mut env = default_env();
+    let result = run(
+        "(date-difference (seconds->date 9223372036854775807) (seconds->date -1))",
+        &mut env,
+    );
+    assert_eq!(
+        result.unwrap_err(),
+        "date-difference: difference out of range"
+    );
+}
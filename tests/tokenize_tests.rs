@@ -180,3 +180,29 @@ fn test_tokenize_if_expression() {
         ]
     );
 }
+
+#[test]
+fn test_tokenize_string_literal_with_spaces() {
+    let tokens = tokenize("(string-length \"hello world\")");
+    assert_eq!(tokens, vec!["(", "string-length", "\"hello world\"", ")"]);
+}
+
+#[test]
+fn test_tokenize_string_literal_with_parens_and_escaped_quote() {
+    let tokens = tokenize(r#"("a (b) \"c\"" x)"#);
+    assert_eq!(tokens, vec!["(", r#""a (b) \"c\"""#, "x", ")"]);
+}
+
+#[test]
+fn test_read_string_literal_escapes() {
+    let text = read_string_literal(r#""a\nb\t\"q\" \\ \x41;\x3bb;""#).unwrap();
+    assert_eq!(text, "a\nb\t\"q\" \\ A\u{3bb}");
+}
+
+#[test]
+fn test_read_string_literal_errors() {
+    assert!(read_string_literal("\"abc").is_err());
+    assert!(read_string_literal(r#""bad \q escape""#).is_err());
+    assert!(read_string_literal(r#""\x41""#).is_err());
+    assert!(read_string_literal(r#""\xzz;""#).is_err());
+}
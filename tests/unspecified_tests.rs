@@ -0,0 +1,93 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_define_returns_unspecified() {
+    let mut env = default_env();
+    assert_eq!(run("(define x 10)", &mut env).unwrap(), Expr::Unspecified);
+    assert_eq!(
+        run("(define f (lambda (x) x))", &mut env).unwrap(),
+        Expr::Unspecified
+    );
+    assert_eq!(run("x", &mut env).unwrap(), Expr::Number(10));
+}
+
+#[test]
+fn test_define_arity_error() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(define x)", &mut env).unwrap_err(),
+        "define requires 2 arguments"
+    );
+}
+
+#[test]
+fn test_set_updates_existing_variable() {
+    let mut env = default_env();
+    run("(define x 1)", &mut env).unwrap();
+    assert_eq!(
+        run("(set! x (+ x 1))", &mut env).unwrap(),
+        Expr::Unspecified
+    );
+    assert_eq!(run("x", &mut env).unwrap(), Expr::Number(2));
+}
+
+#[test]
+fn test_set_requires_bound_variable() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(set! y 1)", &mut env).unwrap_err(),
+        "set!: undefined variable: y"
+    );
+    assert!(run("(set! 1 2)", &mut env).is_err());
+}
+
+#[test]
+fn test_mutators_return_unspecified() {
+    let mut env = default_env();
+    run("(define v (vector 1 2))", &mut env).unwrap();
+    run("(define b (bytevector 1 2))", &mut env).unwrap();
+    run("(define t (make-hash-table))", &mut env).unwrap();
+    for form in [
+        "(vector-set! v 0 5)",
+        "(vector-fill! v 0)",
+        "(bytevector-u8-set! b 0 5)",
+        "(hash-table-set! t 1 2)",
+        "(hash-table-update! t 1 (lambda (n) (+ n 1)))",
+        "(hash-table-walk t (lambda (k v) k))",
+        "(hash-table-delete! t 1)",
+    ] {
+        assert_eq!(run(form, &mut env).unwrap(), Expr::Unspecified, "{}", form);
+    }
+}
+
+#[test]
+fn test_unspecified_display() {
+    assert_eq!(Expr::Unspecified.to_string(), "#<unspecified>");
+    assert_eq!(Expr::Eof.to_string(), "#<eof>");
+}
+
+#[test]
+fn test_eof_object() {
+    let mut env = default_env();
+    assert_eq!(run("(eof-object)", &mut env).unwrap(), Expr::Eof);
+    assert_eq!(
+        run("(eof-object? (eof-object))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    assert_eq!(
+        run("(eof-object? #f)", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(eq? (eof-object) (eof-object))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+    // The end-of-file object is distinct from the unspecified value
+    assert_eq!(
+        run("(eqv? (eof-object) (define z 1))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+}
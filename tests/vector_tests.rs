@@ -0,0 +1,188 @@
+mod common;
+
+use common::run;
+use lisp_interpreter_rs::*;
+
+#[test]
+fn test_tokenize_vector_literal() {
+    assert_eq!(tokenize("#(1 2)"), vec!["#(", "1", "2", ")"]);
+    assert_eq!(tokenize("(f #(a))"), vec!["(", "f", "#(", "a", ")", ")"]);
+}
+
+#[test]
+fn test_vector_literal_is_self_evaluating() {
+    let mut env = default_env();
+    let result = run("#(1 (+ 1 2) x)", &mut env).unwrap();
+    assert_eq!(result.to_string(), "#(1 (+ 1 2) x)");
+}
+
+#[test]
+fn test_vector_evaluates_arguments() {
+    let result = run("(vector 1 (+ 1 1) 3)", &mut default_env()).unwrap();
+    assert_eq!(result.to_string(), "#(1 2 3)");
+}
+
+#[test]
+fn test_make_vector() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(make-vector 3 7)", &mut env).unwrap().to_string(),
+        "#(7 7 7)"
+    );
+    assert_eq!(
+        run("(vector-length (make-vector 4))", &mut env).unwrap(),
+        Expr::Number(4)
+    );
+}
+
+#[test]
+fn test_make_vector_negative_length() {
+    assert!(run("(make-vector -1)", &mut default_env()).is_err());
+}
+
+#[test]
+fn test_make_vector_length_limit() {
+    let result = run("(make-vector 2000000000)", &mut default_env());
+    assert_eq!(
+        result.unwrap_err(),
+        "make-vector: length 2000000000 is more than the maximum of 16777216"
+    );
+}
+
+#[test]
+fn test_vector_ref_and_set() {
+    let mut env = default_env();
+    run("(define v (vector 10 20 30))", &mut env).unwrap();
+    assert_eq!(run("(vector-ref v 1)", &mut env).unwrap(), Expr::Number(20));
+    run("(vector-set! v 1 99)", &mut env).unwrap();
+    assert_eq!(run("(vector-ref v 1)", &mut env).unwrap(), Expr::Number(99));
+}
+
+#[test]
+fn test_vector_mutation_is_shared() {
+    let mut env = default_env();
+    run("(define v (vector 1 2))", &mut env).unwrap();
+    run("(define w v)", &mut env).unwrap();
+    run(
+        "(define set-first (lambda (vec x) (vector-set! vec 0 x)))",
+        &mut env,
+    )
+    .unwrap();
+    run("(set-first w 5)", &mut env).unwrap();
+    assert_eq!(run("v", &mut env).unwrap().to_string(), "#(5 2)");
+}
+
+#[test]
+fn test_vector_index_errors() {
+    let mut env = default_env();
+    run("(define v (vector 1 2 3))", &mut env).unwrap();
+    assert_eq!(
+        run("(vector-ref v 3)", &mut env).unwrap_err(),
+        "vector-ref: index 3 out of range for length 3"
+    );
+    assert_eq!(
+        run("(vector-set! v -1 0)", &mut env).unwrap_err(),
+        "vector-set!: index -1 out of range for length 3"
+    );
+    assert!(run("(vector-ref (quote (1 2)) 0)", &mut env).is_err());
+}
+
+#[test]
+fn test_vector_fill() {
+    let mut env = default_env();
+    run("(define v (vector 1 2 3 4))", &mut env).unwrap();
+    run("(vector-fill! v 0 1 3)", &mut env).unwrap();
+    assert_eq!(run("v", &mut env).unwrap().to_string(), "#(1 0 0 4)");
+    run("(vector-fill! v 9)", &mut env).unwrap();
+    assert_eq!(run("v", &mut env).unwrap().to_string(), "#(9 9 9 9)");
+    assert!(run("(vector-fill! v 0 3 2)", &mut env).is_err());
+}
+
+#[test]
+fn test_vector_list_conversion() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(vector->list #(1 2 3))", &mut env).unwrap(),
+        Expr::List(vec![Expr::Number(1), Expr::Number(2), Expr::Number(3)])
+    );
+    assert_eq!(
+        run("(vector->list #(1 2 3) 1)", &mut env).unwrap(),
+        Expr::List(vec![Expr::Number(2), Expr::Number(3)])
+    );
+    assert_eq!(
+        run("(list->vector (quote (a b)))", &mut env)
+            .unwrap()
+            .to_string(),
+        "#(a b)"
+    );
+}
+
+#[test]
+fn test_vector_map_with_lambda_and_builtin() {
+    let mut env = default_env();
+    assert_eq!(
+        run("(vector-map (lambda (x) (* x x)) #(1 2 3))", &mut env)
+            .unwrap()
+            .to_string(),
+        "#(1 4 9)"
+    );
+    // Built-ins are first-class values; the shortest vector wins
+    assert_eq!(
+        run("(vector-map + #(1 2 3) #(10 20))", &mut env)
+            .unwrap()
+            .to_string(),
+        "#(11 22)"
+    );
+}
+
+#[test]
+fn test_vector_equality() {
+    let mut env = default_env();
+    run("(define v (vector 1 2))", &mut env).unwrap();
+    assert_eq!(run("(eq? v v)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(
+        run("(eqv? v (vector 1 2))", &mut env).unwrap(),
+        Expr::Bool(false)
+    );
+    assert_eq!(
+        run("(equal? v (vector 1 2))", &mut env).unwrap(),
+        Expr::Bool(true)
+    );
+}
+
+#[test]
+fn test_equal_on_vectors_containing_themselves() {
+    let mut env = default_env();
+    run("(define v (make-vector 1 0))", &mut env).unwrap();
+    run("(vector-set! v 0 v)", &mut env).unwrap();
+    run("(define w (make-vector 1 0))", &mut env).unwrap();
+    run("(vector-set! w 0 w)", &mut env).unwrap();
+    assert_eq!(run("(equal? v v)", &mut env).unwrap(), Expr::Bool(true));
+    assert_eq!(run("(equal? v w)", &mut env).unwrap(), Expr::Bool(true));
+    run("(define u (vector v))", &mut env).unwrap();
+    run("(vector-set! w 0 u)", &mut env).unwrap();
+    assert_eq!(run("(equal? v w)", &mut env).unwrap(), Expr::Bool(true));
+    run("(vector-set! u 0 1)", &mut env).unwrap();
+    assert_eq!(run("(equal? v w)", &mut env).unwrap(), Expr::Bool(false));
+}
+
+#[test]
+fn test_printing_a_vector_containing_itself() {
+    let mut env = default_env();
+    run("(define v (vector 1 2))", &mut env).unwrap();
+    run("(vector-set! v 1 (vector v \"s\"))", &mut env).unwrap();
+    let v = run("v", &mut env).unwrap();
+    assert_eq!(v.to_string(), "#(1 #(#<cycle> s))");
+    assert_eq!(v.written().to_string(), "#(1 #(#<cycle> \"s\"))");
+    // The same vector twice, side by side, is not a cycle
+    run("(define x (vector 3))", &mut env).unwrap();
+    let twice = run("(vector x x)", &mut env).unwrap();
+    assert_eq!(twice.to_string(), "#(#(3) #(3))");
+}
+
+#[test]
+fn test_written_vector_with_strings() {
+    let mut env = default_env();
+    let result = run("(vector \"a\" #\\b)", &mut env).unwrap();
+    assert_eq!(result.written().to_string(), "#(\"a\" #\\b)");
+}